@@ -0,0 +1,53 @@
+use anyhow::Result;
+use wordlehelper::{feedback::Feedback, filter, solve, Game};
+
+use crate::{
+    output::{clear_screen, print_words},
+    prompt_feedback, prompt_guess,
+};
+
+// Several boards with different secret words, like Dordle and Quordle. Every guess is played
+// on all boards that aren't solved yet, each giving its own feedback row.
+pub fn play_boards(game: &Game, words: &[String], board_count: usize) -> Result<()> {
+    let mut boards: Vec<(Game, Vec<String>)> = vec![(game.clone(), words.to_vec()); board_count];
+    let mut solved = vec![false; board_count];
+    while solved.contains(&false) {
+        let unsolved: Vec<usize> = (0..board_count).filter(|&index| !solved[index]).collect();
+        for &index in &unsolved {
+            let (game, words) = &boards[index];
+            println!("Board {}, {} possible words:", index + 1, words.len());
+            print_words(words, true, game);
+        }
+        let remaining: Vec<&[String]> = unsolved
+            .iter()
+            .map(|&index| boards[index].1.as_slice())
+            .collect();
+        let best_guesses: Vec<String> = filter::rank_for_boards(&remaining)
+            .into_iter()
+            .take(6)
+            .map(|(word, _)| word)
+            .collect();
+        println!("\nBest guesses for all boards:");
+        print_words(&best_guesses, false, game);
+
+        let Some(guess) = prompt_guess(game, "Guessed word (empty to quit)")? else {
+            return Ok(());
+        };
+        for index in unsolved {
+            let feedback = prompt_feedback(
+                &guess,
+                &format!("Feedback row for board {} (🟩🟨⬛)", index + 1),
+            )?;
+            if feedback.iter().all(|&result| result == Feedback::Green) {
+                solved[index] = true;
+                continue;
+            }
+            let (game, words) = &mut boards[index];
+            game.apply_feedback(&guess, &feedback)?;
+            *words = solve(game, words);
+        }
+        clear_screen();
+    }
+    println!("All boards solved!");
+    Ok(())
+}
@@ -0,0 +1,616 @@
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+use wordlehelper::{
+    compose_letters,
+    feedback::{parse_code_feedback, Feedback},
+    filter::{
+        CommonLetters, CommonLettersAndVowels, Entropy, FreshLetters, Minimax, ScoringStrategy,
+        TieBreak,
+    },
+    wordlist::InvalidWords,
+    GameLanguage, GameLength, GuessSource,
+};
+
+use crate::config::Config;
+
+pub const USAGE: &str = "\
+Usage: wordlehelper [OPTIONS]
+
+Without options an interactive game is started. Defaults for the language, length, word lists,
+colors and max print can be set in ~/.config/wordlehelper/config.toml, like language = \"english\".
+
+Options:
+      --language <LANGUAGE>  Game language, swedish, english, german, spanish, french,
+                             italian, portuguese, norwegian or danish, or its code like sv or en
+      --length <LENGTH>      Number of letters, 4 to 8. Bundled lists have 5, or 6 for swedish
+      --playfield <LETTERS>  CAPITAL letters in correct slot, lower case in the wrong slot, - if empty
+      --wrong <LETTERS>      Letters not in the word
+      --first-guess <WORD>   The word you always start with, played before the first prompt
+      --first-pattern <GYB>  Colors of the first guess, G for green, Y yellow and B gray
+      --present <LETTERS>    Letters in the word but not in the given slots, like \"r:1,3 t:2\"
+      --fold-accents         Letters with accents match the same letter without them, e matches é
+      --wordlist <PATH>      Newline separated word list to use instead of the bundled ones,
+                             optionally with a frequency after each word like \"slate 482913\"
+      --extra-wordlist <PATH>
+                             More words to add to the bundled list or --wordlist, in the same
+                             format as --wordlist
+      --strict-wordlist      Fail when a word in --wordlist has other characters than letters,
+                             like can't, instead of skipping it
+      --guesses <PATH>       Words that can be guessed but aren't possible answers, suggested when
+                             they tell more than any possible word
+      --answers-only         Only suggest words that can be the answer, even with --guesses
+      --keep-duplicates      Don't filter out suggestions with repeated letters, like ERROR
+      --coverage             Show how many of the letters left in the possible words each best
+                             guess contains
+      --format <FORMAT>      Output format, text or json
+      --resume <PATH>        Continue a game saved with \"Save and quit\"
+      --save-on-interrupt <PATH>
+                             Save the session to the file when the game is stopped with Ctrl-C
+      --boards <COUNT>       Play several boards with the same guesses, 2 for Dordle, 4 for Quordle
+      --stats-file <PATH>    Add the result of each game as a JSON line to the file
+      --export <PATH>        Write the possible words to the file, one per line, after each guess
+      --simulate <WORD>      Let the solver play against a secret word and print its guesses
+      --puzzle <N>           Simulate against the answer of daily puzzle N, from 0. Needs a
+                             --wordlist with the answers in the order they were used
+      --benchmark            Let the solver play against every word in the list and print how
+                             many guesses it needed
+      --strategy <STRATEGY>  Rank guesses by entropy (most information), minimax (smallest worst case),
+                             common (most common letters), vowels (common letters, then the
+                             most different vowels) or fresh (most letters not tried yet)
+      --max-print <N>        Print at most this many possible words, 30 if not given
+      --seed <N>             Shuffle suggested words with the same score, the same way for the
+                             same seed, instead of sorting them alphabetically
+      --scores               Print the score of each suggested word
+      --batch                Read lines like \"guess=slate pattern=BGBYB\" from stdin and print the
+                             words left. G is green, Y yellow and B gray
+      --use-defaults         Don't ask for the language and length set in the config file
+      --keep-going           Keep asking for clues when one word is left, to check it matches them
+      --repl                 Type commands like \"play s_a_e\" instead of choosing from menus
+      --quiet                Only print the words, without the messages about how they were filtered
+      --no-color             Print words without highlighting green and yellow letters, like
+                             when NO_COLOR is set
+      --no-clear             Print a line between guesses instead of clearing the screen
+  -h, --help                 Print help
+  -V, --version              Print the version and the hashes of the bundled word lists";
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    // All possible words, the ones without duplicate letters and the best guesses as a JSON object
+    Json,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Strategy {
+    // Most expected information
+    #[default]
+    Entropy,
+    // Smallest worst case number of words left
+    Minimax,
+    // Most common letters of the language
+    CommonLetters,
+    // Most common letters, then the most distinct vowels
+    Vowels,
+    // Most letters that no guess has tried yet
+    Fresh,
+}
+
+impl Strategy {
+    pub fn scoring(self) -> &'static dyn ScoringStrategy {
+        match self {
+            Self::Entropy => &Entropy,
+            Self::Minimax => &Minimax,
+            Self::CommonLetters => &CommonLetters,
+            Self::Vowels => &CommonLettersAndVowels,
+            Self::Fresh => &FreshLetters,
+        }
+    }
+}
+
+// Each flag without a value is a bool
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default)]
+pub struct Args {
+    pub language: Option<GameLanguage>,
+    pub length: Option<GameLength>,
+    pub playfield: Option<String>,
+    pub wrong: Option<String>,
+    pub present: Option<String>,
+    pub first_guess: Option<String>,
+    pub first_pattern: Option<String>,
+    pub wordlist: Option<PathBuf>,
+    pub extra_wordlist: Option<PathBuf>,
+    pub guesses: Option<PathBuf>,
+    pub format: OutputFormat,
+    pub strategy: Strategy,
+    pub resume: Option<PathBuf>,
+    pub save_on_interrupt: Option<PathBuf>,
+    pub simulate: Option<String>,
+    pub puzzle: Option<usize>,
+    pub stats_file: Option<PathBuf>,
+    pub export: Option<PathBuf>,
+    pub boards: usize,
+    pub max_print: Option<usize>,
+    pub seed: Option<u64>,
+    pub no_color: bool,
+    pub quiet: bool,
+    pub no_clear: bool,
+    pub scores: bool,
+    pub repl: bool,
+    pub keep_going: bool,
+    pub use_defaults: bool,
+    // From the config file, used where no language or length is given
+    pub default_language: Option<GameLanguage>,
+    pub default_length: Option<GameLength>,
+    pub batch: bool,
+    pub benchmark: bool,
+    pub fold_accents: bool,
+    pub strict_wordlist: bool,
+    pub answers_only: bool,
+    pub keep_duplicates: bool,
+    pub coverage: bool,
+    pub help: bool,
+    pub version: bool,
+}
+
+impl Args {
+    pub fn parse() -> Result<Self> {
+        Self::parse_from(std::env::args().skip(1))
+    }
+
+    pub fn parse_from<I>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            if let Some(enabled) = parsed.bool_flag(&flag) {
+                if inline_value.is_some() {
+                    bail!("{} doesn't take a value", flag);
+                }
+                *enabled = true;
+                continue;
+            }
+            let value = match inline_value {
+                Some(value) => value,
+                None => args
+                    .next()
+                    .with_context(|| format!("Missing value for {}", flag))?,
+            };
+            match flag.as_str() {
+                "--language" => parsed.language = Some(value.parse()?),
+                "--length" => parsed.length = Some(value.parse()?),
+                "--playfield" => parsed.playfield = Some(value),
+                "--wrong" => parsed.wrong = Some(value),
+                "--present" => parsed.present = Some(value),
+                "--first-guess" => parsed.first_guess = Some(value),
+                "--first-pattern" => parsed.first_pattern = Some(value),
+                "--wordlist" => parsed.wordlist = Some(PathBuf::from(value)),
+                "--extra-wordlist" => parsed.extra_wordlist = Some(PathBuf::from(value)),
+                "--guesses" => parsed.guesses = Some(PathBuf::from(value)),
+                "--format" => parsed.format = parse_format(&value)?,
+                "--strategy" => parsed.strategy = parse_strategy(&value)?,
+                "--resume" => parsed.resume = Some(PathBuf::from(value)),
+                "--save-on-interrupt" => parsed.save_on_interrupt = Some(PathBuf::from(value)),
+                "--simulate" => parsed.simulate = Some(value),
+                "--puzzle" => parsed.puzzle = Some(parse_number("Puzzle", &value)?),
+                "--stats-file" => parsed.stats_file = Some(PathBuf::from(value)),
+                "--export" => parsed.export = Some(PathBuf::from(value)),
+                "--boards" => parsed.boards = parse_boards(&value)?,
+                "--max-print" => parsed.max_print = Some(parse_number("Max print", &value)?),
+                "--seed" => parsed.seed = Some(parse_number("Seed", &value)?),
+                _ => bail!("Unknown option {}\n\n{}", flag, USAGE),
+            }
+        }
+        Ok(parsed)
+    }
+
+    // The field of a flag that takes no value, like --quiet
+    fn bool_flag(&mut self, flag: &str) -> Option<&mut bool> {
+        Some(match flag {
+            "-h" | "--help" => &mut self.help,
+            "-V" | "--version" => &mut self.version,
+            "--quiet" => &mut self.quiet,
+            "--no-color" => &mut self.no_color,
+            "--no-clear" => &mut self.no_clear,
+            "--scores" => &mut self.scores,
+            "--repl" => &mut self.repl,
+            "--keep-going" => &mut self.keep_going,
+            "--use-defaults" => &mut self.use_defaults,
+            "--batch" => &mut self.batch,
+            "--benchmark" => &mut self.benchmark,
+            "--fold-accents" => &mut self.fold_accents,
+            "--strict-wordlist" => &mut self.strict_wordlist,
+            "--answers-only" => &mut self.answers_only,
+            "--keep-duplicates" => &mut self.keep_duplicates,
+            "--coverage" => &mut self.coverage,
+            _ => return None,
+        })
+    }
+
+    // The first guess and its colors, they must be given together
+    pub fn first_guess(&self) -> Result<Option<(String, Vec<Feedback>)>> {
+        match (&self.first_guess, &self.first_pattern) {
+            (Some(guess), Some(pattern)) => {
+                let guess = compose_letters(guess.trim()).to_lowercase();
+                let feedback = parse_code_feedback(&guess, pattern)?;
+                Ok(Some((guess, feedback)))
+            }
+            (None, None) => Ok(None),
+            _ => bail!("--first-guess and --first-pattern must be given together"),
+        }
+    }
+
+    // Options not given on the command line are taken from the config. The language and length
+    // are kept apart, as giving them on the command line starts a game without prompts.
+    pub fn apply_config(&mut self, config: Config) {
+        self.wordlist = self.wordlist.take().or(config.wordlist);
+        self.guesses = self.guesses.take().or(config.guesses);
+        self.max_print = self.max_print.or(config.max_print);
+        if config.color == Some(false) {
+            self.no_color = true;
+        }
+        self.default_language = config.language;
+        self.default_length = config.length;
+    }
+
+    pub fn language_or_default(&self) -> GameLanguage {
+        self.language
+            .or(self.default_language)
+            .unwrap_or(GameLanguage::English)
+    }
+
+    pub const fn invalid_words(&self) -> InvalidWords {
+        if self.strict_wordlist {
+            InvalidWords::Reject
+        } else {
+            InvalidWords::Skip
+        }
+    }
+
+    pub const fn tie_break(&self) -> TieBreak {
+        match self.seed {
+            Some(seed) => TieBreak::Seeded(seed),
+            None => TieBreak::Alphabetical,
+        }
+    }
+
+    // Guesses are ranked from the allowed guesses when there is a separate list of them, unless
+    // only words that can still be the answer should be suggested
+    pub const fn guess_source(&self) -> GuessSource {
+        if self.guesses.is_some() && !self.answers_only {
+            GuessSource::AllowedGuesses
+        } else {
+            GuessSource::PossibleWords
+        }
+    }
+
+    // Any game option or JSON output skips the interactive prompts
+    pub fn is_non_interactive(&self) -> bool {
+        self.format == OutputFormat::Json
+            || self.language.is_some()
+            || self.length.is_some()
+            || self.playfield.is_some()
+            || self.wrong.is_some()
+            || self.present.is_some()
+    }
+}
+
+fn parse_format(value: &str) -> Result<OutputFormat> {
+    match value.to_lowercase().as_str() {
+        "text" => Ok(OutputFormat::Text),
+        "json" => Ok(OutputFormat::Json),
+        _ => bail!("Unknown format {}, expected text or json", value),
+    }
+}
+
+fn parse_number<T: FromStr>(name: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("{} {} is not a number", name, value))
+}
+
+fn parse_strategy(value: &str) -> Result<Strategy> {
+    match value.to_lowercase().as_str() {
+        "entropy" => Ok(Strategy::Entropy),
+        "minimax" => Ok(Strategy::Minimax),
+        "common" => Ok(Strategy::CommonLetters),
+        "vowels" => Ok(Strategy::Vowels),
+        "fresh" => Ok(Strategy::Fresh),
+        _ => bail!(
+            "Unknown strategy {}, expected entropy, minimax, common, vowels or fresh",
+            value
+        ),
+    }
+}
+
+fn parse_boards(value: &str) -> Result<usize> {
+    match value.parse() {
+        Ok(boards @ 1..=8) => Ok(boards),
+        _ => bail!("Boards {} is not a number from 1 to 8", value),
+    }
+}
+
+// "r:1,3 t:2" is r in the word but not in slot 1 or 3, and t not in slot 2. Returns the letters
+// excluded from each slot.
+pub fn parse_present(
+    value: &str,
+    language: GameLanguage,
+    length: GameLength,
+) -> Result<Vec<Vec<char>>> {
+    let mut excluded_positions = vec![Vec::new(); length as usize];
+    for entry in value.split_whitespace() {
+        let (letter, slots) = entry
+            .split_once(':')
+            .with_context(|| format!("Expected letter:slots like r:1,3, found {}", entry))?;
+        let mut letters = letter.chars().flat_map(char::to_lowercase);
+        let (Some(letter), None) = (letters.next(), letters.next()) else {
+            bail!("Expected a single letter before : in {}", entry);
+        };
+        if !language.is_letter(letter) {
+            bail!("{} is not a letter", letter);
+        }
+        for slot in slots.split(',') {
+            let index = match slot.parse::<usize>() {
+                Ok(slot @ 1..) if slot <= length as usize => slot - 1,
+                _ => bail!(
+                    "Slot {} for {} is not a number from 1 to {}",
+                    slot,
+                    letter,
+                    length as usize
+                ),
+            };
+            if !excluded_positions[index].contains(&letter) {
+                excluded_positions[index].push(letter);
+            }
+        }
+    }
+    Ok(excluded_positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args> {
+        Args::parse_from(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse(&[
+            "--language",
+            "english",
+            "--length=5",
+            "--playfield",
+            "-A--T",
+            "--wrong",
+            "rsn",
+        ])
+        .unwrap();
+        assert_eq!(args.language, Some(GameLanguage::English));
+        assert!(matches!(args.length, Some(GameLength::Five)));
+        assert_eq!(args.playfield.as_deref(), Some("-A--T"));
+        assert_eq!(args.wrong.as_deref(), Some("rsn"));
+        assert!(args.is_non_interactive());
+
+        let args = parse(&["--wordlist", "words.txt"]).unwrap();
+        assert_eq!(args.wordlist, Some(PathBuf::from("words.txt")));
+        let args = parse(&["--extra-wordlist", "mine.txt"]).unwrap();
+        assert_eq!(args.extra_wordlist, Some(PathBuf::from("mine.txt")));
+        let args = parse(&["--guesses", "guesses.txt"]).unwrap();
+        assert_eq!(args.guesses, Some(PathBuf::from("guesses.txt")));
+        assert!(!args.is_non_interactive());
+        assert_eq!(args.guess_source(), GuessSource::AllowedGuesses);
+
+        let args = parse(&["--format", "json"]).unwrap();
+        assert_eq!(args.format, OutputFormat::Json);
+        assert!(args.is_non_interactive());
+
+        assert_eq!(parse(&[]).unwrap().strategy, Strategy::Entropy);
+        let args = parse(&["--strategy", "minimax"]).unwrap();
+        assert_eq!(args.strategy, Strategy::Minimax);
+        let args = parse(&["--strategy", "common"]).unwrap();
+        assert_eq!(args.strategy, Strategy::CommonLetters);
+        let args = parse(&["--strategy", "vowels"]).unwrap();
+        assert_eq!(args.strategy, Strategy::Vowels);
+        let args = parse(&["--strategy", "fresh"]).unwrap();
+        assert_eq!(args.strategy, Strategy::Fresh);
+        assert!(!args.is_non_interactive());
+
+        assert!(parse(&["--no-color"]).unwrap().no_color);
+        assert!(parse(&["--keep-duplicates"]).unwrap().keep_duplicates);
+        assert!(parse(&["--coverage"]).unwrap().coverage);
+        assert!(parse(&["--quiet"]).unwrap().quiet);
+        assert!(parse(&["--no-clear"]).unwrap().no_clear);
+        assert!(parse(&["--scores"]).unwrap().scores);
+        assert!(parse(&["--repl"]).unwrap().repl);
+        assert!(parse(&["--keep-going"]).unwrap().keep_going);
+        assert!(parse(&["--use-defaults"]).unwrap().use_defaults);
+        assert!(parse(&["--batch"]).unwrap().batch);
+        assert!(parse(&["--benchmark"]).unwrap().benchmark);
+        assert!(parse(&["--version"]).unwrap().version);
+        assert!(parse(&["-V"]).unwrap().version);
+        assert!(parse(&["--fold-accents"]).unwrap().fold_accents);
+        let args = parse(&["--strict-wordlist"]).unwrap();
+        assert_eq!(args.invalid_words(), InvalidWords::Reject);
+        assert_eq!(parse(&[]).unwrap().invalid_words(), InvalidWords::Skip);
+        let args = parse(&["--language", "French"]).unwrap();
+        assert_eq!(args.language, Some(GameLanguage::French));
+        let args = parse(&["--language", "portuguese"]).unwrap();
+        assert_eq!(args.language, Some(GameLanguage::Portuguese));
+        let args = parse(&["--language", "no"]).unwrap();
+        assert_eq!(args.language, Some(GameLanguage::Norwegian));
+        assert_eq!(parse(&["--boards", "4"]).unwrap().boards, 4);
+        assert_eq!(parse(&["--max-print", "100"]).unwrap().max_print, Some(100));
+        assert_eq!(parse(&[]).unwrap().max_print, None);
+        let args = parse(&["--seed", "42"]).unwrap();
+        assert_eq!(args.tie_break(), TieBreak::Seeded(42));
+        assert_eq!(parse(&[]).unwrap().tie_break(), TieBreak::Alphabetical);
+
+        let args = parse(&["--stats-file", "stats.jsonl"]).unwrap();
+        assert_eq!(args.stats_file, Some(PathBuf::from("stats.jsonl")));
+        let args = parse(&["--export", "words.txt"]).unwrap();
+        assert_eq!(args.export, Some(PathBuf::from("words.txt")));
+
+        let args = parse(&["--simulate", "cigar"]).unwrap();
+        assert_eq!(args.simulate.as_deref(), Some("cigar"));
+        assert_eq!(parse(&["--puzzle", "0"]).unwrap().puzzle, Some(0));
+        assert!(parse(&["--puzzle", "latest"]).is_err());
+
+        let args = parse(&["--resume", "game.session"]).unwrap();
+        assert_eq!(args.resume, Some(PathBuf::from("game.session")));
+        assert!(!args.is_non_interactive());
+        let args = parse(&["--save-on-interrupt", "game.session"]).unwrap();
+        assert_eq!(args.save_on_interrupt, Some(PathBuf::from("game.session")));
+    }
+
+    #[test]
+    fn test_answers_only() {
+        let args = parse(&["--guesses", "guesses.txt", "--answers-only"]).unwrap();
+        assert_eq!(args.guess_source(), GuessSource::PossibleWords);
+        assert_eq!(
+            parse(&["--answers-only"]).unwrap().guess_source(),
+            GuessSource::PossibleWords
+        );
+
+        // A probe word from the guess list isn't suggested, only the possible answers are
+        let mut game = wordlehelper::Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-', 'I', 'G', 'H', 'T'],
+            vec![],
+        );
+        let answers = ["fight", "light", "might"].map(String::from);
+        let allowed_guesses = ["flame", "fight", "light", "might"].map(String::from);
+        game.guess_source = args.guess_source();
+        let pool = wordlehelper::filter::guess_pool(&game, &answers, &allowed_guesses);
+        assert!(!pool.contains(&"flame".to_string()));
+        game.guess_source = GuessSource::AllowedGuesses;
+        let pool = wordlehelper::filter::guess_pool(&game, &answers, &allowed_guesses);
+        assert!(pool.contains(&"flame".to_string()));
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let config = || Config {
+            language: Some(GameLanguage::Swedish),
+            length: Some(GameLength::Six),
+            wordlist: Some(PathBuf::from("config.txt")),
+            guesses: Some(PathBuf::from("guesses.txt")),
+            color: Some(false),
+            max_print: Some(100),
+        };
+        let mut args = parse(&[]).unwrap();
+        args.apply_config(config());
+        assert_eq!(args.wordlist, Some(PathBuf::from("config.txt")));
+        assert_eq!(args.guesses, Some(PathBuf::from("guesses.txt")));
+        assert_eq!(args.max_print, Some(100));
+        assert!(args.no_color);
+        assert_eq!(args.language_or_default(), GameLanguage::Swedish);
+        assert_eq!(args.default_length, Some(GameLength::Six));
+        // Defaults don't turn an interactive game into one without prompts
+        assert!(!args.is_non_interactive());
+
+        let mut args = parse(&[
+            "--language",
+            "german",
+            "--wordlist",
+            "words.txt",
+            "--max-print",
+            "10",
+        ])
+        .unwrap();
+        args.apply_config(config());
+        assert_eq!(args.language_or_default(), GameLanguage::German);
+        assert_eq!(args.wordlist, Some(PathBuf::from("words.txt")));
+        assert_eq!(args.max_print, Some(10));
+
+        let mut args = parse(&[]).unwrap();
+        args.apply_config(Config::default());
+        assert_eq!(args.language_or_default(), GameLanguage::English);
+        assert!(!args.no_color);
+    }
+
+    #[test]
+    fn test_first_guess() {
+        use wordlehelper::{solve, wordlist::read_file, Game};
+
+        let args = parse(&["--first-guess", "Slate", "--first-pattern", "bgbyb"]).unwrap();
+        let (guess, feedback) = args.first_guess().unwrap().unwrap();
+        assert_eq!(guess, "slate");
+        let mut seeded = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        seeded.apply_feedback(&guess, &feedback).unwrap();
+
+        let mut entered = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            "-L-t-".chars().collect(),
+            vec!['s', 'a', 'e'],
+        );
+        entered.update_letter_counts();
+        entered.record_misplaced_letters();
+
+        let words = read_file(&entered, None).unwrap();
+        let narrowed = solve(&seeded, &words);
+        assert!(!narrowed.is_empty());
+        assert_eq!(narrowed, solve(&entered, &words));
+
+        assert_eq!(parse(&[]).unwrap().first_guess().unwrap(), None);
+        assert!(parse(&["--first-guess", "slate"])
+            .unwrap()
+            .first_guess()
+            .is_err());
+        assert!(parse(&["--first-guess", "slate", "--first-pattern", "BGB"])
+            .unwrap()
+            .first_guess()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_present() {
+        let excluded = parse_present("r:1,3 T:2", GameLanguage::English, GameLength::Five).unwrap();
+        assert_eq!(excluded, [vec!['r'], vec!['t'], vec!['r'], vec![], vec![]]);
+        let excluded = parse_present("r:1 a:1", GameLanguage::English, GameLength::Four).unwrap();
+        assert_eq!(excluded, [vec!['r', 'a'], vec![], vec![], vec![]]);
+
+        for invalid in ["r:0", "r:6", "r:two", "r", "rt:1", "1:2", "å:1"] {
+            assert!(
+                parse_present(invalid, GameLanguage::English, GameLength::Five).is_err(),
+                "{}",
+                invalid
+            );
+        }
+        assert!(parse_present("å:1", GameLanguage::Swedish, GameLength::Five).is_ok());
+        assert!(parse(&["--present", "r:1,3"]).unwrap().is_non_interactive());
+    }
+
+    #[test]
+    fn test_parse_args_interactive_and_errors() {
+        assert!(!parse(&[]).unwrap().is_non_interactive());
+        assert!(parse(&["--language", "klingon"]).is_err());
+        assert!(parse(&["--length", "9"]).is_err());
+        assert!(parse(&["--length", "five"]).is_err());
+        assert!(parse(&["--playfield"]).is_err());
+        assert!(parse(&["--unknown", "x"]).is_err());
+        assert!(parse(&["--strategy", "random"]).is_err());
+        assert!(parse(&["--boards", "0"]).is_err());
+        assert!(parse(&["--max-print", "many"]).is_err());
+        assert!(parse(&["--seed", "-1"]).is_err());
+        let error = parse(&["--quiet=no"]).unwrap_err();
+        assert_eq!(error.to_string(), "--quiet doesn't take a value");
+        assert!(parse(&["--no-color=false"]).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use std::{env, fs, io, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use wordlehelper::{GameLanguage, GameLength};
+
+// Defaults from the config file, anything given on the command line is used instead
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    pub language: Option<GameLanguage>,
+    pub length: Option<GameLength>,
+    pub wordlist: Option<PathBuf>,
+    pub guesses: Option<PathBuf>,
+    pub color: Option<bool>,
+    pub max_print: Option<usize>,
+}
+
+// $XDG_CONFIG_HOME/wordlehelper/config.toml, or ~/.config/wordlehelper/config.toml
+pub fn config_path() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".config")))?;
+    Some(config_home.join("wordlehelper").join("config.toml"))
+}
+
+// No config file is the same as an empty one
+pub fn load_config() -> Result<Config> {
+    let Some(path) = config_path() else {
+        return Ok(Config::default());
+    };
+    match fs::read_to_string(&path) {
+        Ok(text) => parse_config(&text).with_context(|| format!("Invalid {}", path.display())),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(error) => {
+            Err(error).with_context(|| format!("Failed to read config {}", path.display()))
+        }
+    }
+}
+
+// The part of TOML the config needs, lines like language = "english" or max_print = 100 with
+// # comments. Paths starting with ~/ are in the home directory.
+pub fn parse_config(text: &str) -> Result<Config> {
+    let mut config = Config::default();
+    for (index, line) in text.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        parse_line(&mut config, line).with_context(|| format!("Line {}", index + 1))?;
+    }
+    Ok(config)
+}
+
+fn parse_line(config: &mut Config, line: &str) -> Result<()> {
+    let Some((key, value)) = line.split_once('=') else {
+        bail!("Expected key = value, found {}", line);
+    };
+    let value = value.trim();
+    match key.trim() {
+        "language" => config.language = Some(string(value)?.parse()?),
+        "length" => config.length = Some(value.parse()?),
+        "wordlist" => config.wordlist = Some(expand_home(&string(value)?)),
+        "guesses" => config.guesses = Some(expand_home(&string(value)?)),
+        "color" => config.color = Some(boolean(value)?),
+        "max_print" => {
+            config.max_print = Some(
+                value
+                    .parse()
+                    .with_context(|| format!("max_print {} is not a number", value))?,
+            );
+        }
+        key => bail!(
+            "Unknown key {}, expected language, length, wordlist, guesses, color or \
+             max_print",
+            key
+        ),
+    }
+    Ok(())
+}
+
+// A # starts a comment unless it's inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn string(value: &str) -> Result<String> {
+    let Some(quoted) = value
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        bail!("Expected a string in double quotes, found {}", value);
+    };
+    let mut unescaped = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('\\') => unescaped.push('\\'),
+                Some('"') => unescaped.push('"'),
+                _ => bail!("Unsupported escape in {}", value),
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    Ok(unescaped)
+}
+
+fn boolean(value: &str) -> Result<bool> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => bail!("Expected true or false, found {}", value),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = parse_config(
+            "# Always english\n\
+             language = \"English\"\n\
+             length = 5\n\
+             \n\
+             wordlist = \"/usr/share/words #5.txt\"  # a comment\n\
+             guesses = \"guesses \\\"all\\\".txt\"\n\
+             color = false\n\
+             max_print = 100\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                language: Some(GameLanguage::English),
+                length: Some(GameLength::Five),
+                wordlist: Some(PathBuf::from("/usr/share/words #5.txt")),
+                guesses: Some(PathBuf::from("guesses \"all\".txt")),
+                color: Some(false),
+                max_print: Some(100),
+            }
+        );
+        assert_eq!(parse_config("").unwrap(), Config::default());
+
+        for invalid in [
+            "language = english",
+            "language = \"klingon\"",
+            "length = 3",
+            "color = yes",
+            "max_print = many",
+            "theme = \"dark\"",
+            "[defaults]",
+        ] {
+            assert!(parse_config(invalid).is_err(), "{}", invalid);
+        }
+        let error = parse_config("length = 5\ncolor = 1").unwrap_err();
+        assert!(format!("{:#}", error).starts_with("Line 2"));
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+
+use crate::{solve, to_uppercase, Game};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Feedback {
+    Green,
+    Yellow,
+    Gray,
+}
+
+// The feedback Wordle gives for a guess when the answer is known, both in the same case. Letters
+// in the correct slot are counted first, so a duplicate letter is only green or yellow as many
+// times as it occurs in the answer.
+pub fn compute_feedback(guess: &str, answer: &str) -> Vec<Feedback> {
+    let guess: Vec<char> = guess.chars().collect();
+    let answer: Vec<char> = answer.chars().collect();
+    let mut feedback = vec![Feedback::Gray; guess.len()];
+    let mut unmatched: HashMap<char, usize> = HashMap::new();
+    for (index, (&g, &a)) in guess.iter().zip(&answer).enumerate() {
+        if g == a {
+            feedback[index] = Feedback::Green;
+        } else {
+            *unmatched.entry(a).or_default() += 1;
+        }
+    }
+    for (index, g) in guess.iter().enumerate() {
+        if feedback[index] == Feedback::Gray {
+            if let Some(count) = unmatched.get_mut(g).filter(|count| **count > 0) {
+                *count -= 1;
+                feedback[index] = Feedback::Yellow;
+            }
+        }
+    }
+    feedback
+}
+
+// Read a row of Wordle emoji, white and black squares both mean the letter is not in the word.
+// Orange and blue are the high contrast versions of green and yellow.
+pub fn parse_emoji_feedback(guess: &str, pattern: &str) -> Result<Vec<Feedback>> {
+    let mut feedback = Vec::new();
+    // Skip the variation selector some platforms add after the squares
+    for square in pattern.trim().chars().filter(|&c| c != '\u{fe0f}') {
+        feedback.push(match square {
+            '🟩' | '🟧' => Feedback::Green,
+            '🟨' | '🟦' => Feedback::Yellow,
+            '⬛' | '⬜' => Feedback::Gray,
+            _ => bail!("Unknown square {} in feedback row", square),
+        });
+    }
+    if feedback.len() != guess.chars().count() {
+        bail!(
+            "Feedback row has {} squares but the guess has {} letters",
+            feedback.len(),
+            guess.chars().count()
+        );
+    }
+    Ok(feedback)
+}
+
+// Read a Wordle code in any case, G for green, Y for yellow and B (black) for gray
+pub fn parse_code_feedback(guess: &str, code: &str) -> Result<Vec<Feedback>> {
+    let mut feedback = Vec::new();
+    for letter in code.trim().chars() {
+        feedback.push(match letter.to_ascii_uppercase() {
+            'G' => Feedback::Green,
+            'Y' => Feedback::Yellow,
+            'B' => Feedback::Gray,
+            _ => bail!("Unknown letter {} in code, expected G, Y or B", letter),
+        });
+    }
+    if feedback.len() != guess.chars().count() {
+        bail!(
+            "Code has {} letters but the guess has {} letters",
+            feedback.len(),
+            guess.chars().count()
+        );
+    }
+    Ok(feedback)
+}
+
+// A batch line like "guess=slate pattern=BGBYB"
+pub fn parse_batch_line(line: &str) -> Result<(String, Vec<Feedback>)> {
+    let mut guess = None;
+    let mut pattern = None;
+    for field in line.split_whitespace() {
+        match field.split_once('=') {
+            Some(("guess", value)) => guess = Some(value.to_lowercase()),
+            Some(("pattern", value)) => pattern = Some(value),
+            _ => bail!("Unknown field {}, expected guess=WORD pattern=GYB", field),
+        }
+    }
+    let guess = guess.context("Missing guess=WORD")?;
+    let pattern = pattern.context("Missing pattern=GYB")?;
+    let feedback = parse_code_feedback(&guess, pattern)?;
+    Ok((guess, feedback))
+}
+
+// Apply the clues of each batch line and return the words left. Blank lines and lines
+// starting with # are skipped.
+pub fn apply_batch<W, I, S>(game: &mut Game, words: &[W], lines: I) -> Result<Vec<W>>
+where
+    W: AsRef<str> + Clone + Send + Sync,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    for (index, line) in lines.into_iter().enumerate() {
+        let line = line.as_ref().trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (guess, feedback) =
+            parse_batch_line(line).with_context(|| format!("Line {}: {}", index + 1, line))?;
+        game.apply_feedback(&guess, &feedback)
+            .with_context(|| format!("Line {}: {}", index + 1, line))?;
+    }
+    Ok(solve(game, words))
+}
+
+impl Game {
+    // A guess picked from the last few possible words, without feedback it was the answer and
+    // every letter is green
+    pub fn apply_pick(&mut self, guess: &str, feedback: Option<&[Feedback]>) -> Result<()> {
+        match feedback {
+            Some(feedback) => self.apply_feedback(guess, feedback),
+            None => self.apply_feedback(guess, &compute_feedback(guess, guess)),
+        }
+    }
+
+    // Lock green letters in the playfield, remember yellow letters for their slot and add gray
+    // letters to the wrong letters. A gray letter that is also green or yellow in the same guess
+    // caps how many times the letter occurs instead.
+    pub fn apply_feedback(&mut self, guess: &str, feedback: &[Feedback]) -> Result<()> {
+        let guess: Vec<char> = guess.to_lowercase().chars().collect();
+        if guess.len() != self.length as usize || feedback.len() != guess.len() {
+            bail!(
+                "Guess and feedback must have {} letters",
+                self.length as usize
+            );
+        }
+
+        for (index, (&letter, &result)) in guess.iter().zip(feedback).enumerate() {
+            let found = guess
+                .iter()
+                .zip(feedback)
+                .filter(|&(&c, &f)| c == letter && f != Feedback::Gray)
+                .count();
+            match result {
+                Feedback::Green => self.playfield[index] = to_uppercase(letter),
+                Feedback::Yellow => {
+                    if !self.excluded_positions[index].contains(&letter) {
+                        self.excluded_positions[index].push(letter);
+                    }
+                }
+                Feedback::Gray if found > 0 => {
+                    if !self.excluded_positions[index].contains(&letter) {
+                        self.excluded_positions[index].push(letter);
+                    }
+                }
+                Feedback::Gray => {
+                    if !self.wrong_letters.contains(&letter) {
+                        self.wrong_letters.push(letter);
+                    }
+                }
+            }
+
+            let length = self.length as usize;
+            let (min, max) = self.letter_counts.entry(letter).or_insert((0, length));
+            *min = (*min).max(found);
+            if result == Feedback::Gray {
+                *max = (*max).min(found);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve, GameLanguage, GameLength};
+
+    #[test]
+    fn test_compute_feedback() {
+        let code = |guess, answer| -> String {
+            compute_feedback(guess, answer)
+                .into_iter()
+                .map(|feedback| match feedback {
+                    Feedback::Green => 'G',
+                    Feedback::Yellow => 'Y',
+                    Feedback::Gray => 'B',
+                })
+                .collect()
+        };
+        assert_eq!(code("slate", "slate"), "GGGGG");
+        assert_eq!(code("crane", "sloth"), "BBBBB");
+        assert_eq!(code("slate", "sandy"), "GBYBB");
+        // Both e:s of speed are yellow since erase has two, but there's only one s
+        assert_eq!(code("speed", "erase"), "YBYYB");
+        assert_eq!(code("erase", "speed"), "YBBYY");
+        // The green e uses up one of the two e:s in there
+        assert_eq!(code("eerie", "there"), "YBYBG");
+        assert_eq!(code("geese", "those"), "BBBGG");
+        assert_eq!(code("abbey", "babes"), "YYGGB");
+        assert_eq!(code("llama", "hello"), "YYBBB");
+        assert_eq!(code("hello", "llama"), "BBYYB");
+        assert_eq!(code("error", "robot"), "BYBGB");
+        assert_eq!(code("sassy", "asses"), "YYGYB");
+        assert_eq!(code("ÖVRIG", "ÖVRIG"), "GGGGG");
+        assert_eq!(code("", ""), "");
+    }
+
+    #[test]
+    fn test_parse_emoji_feedback() {
+        let feedback = parse_emoji_feedback("slate", "🟩🟨⬛⬜️⬛").unwrap();
+        assert_eq!(
+            feedback,
+            [
+                Feedback::Green,
+                Feedback::Yellow,
+                Feedback::Gray,
+                Feedback::Gray,
+                Feedback::Gray
+            ]
+        );
+        assert!(parse_emoji_feedback("slate", "🟩🟨⬛").is_err());
+        assert!(parse_emoji_feedback("slate", "🟩🟨⬛x⬛").is_err());
+    }
+
+    #[test]
+    fn test_parse_code_feedback() {
+        assert_eq!(
+            parse_code_feedback("slate", "GyBbY").unwrap(),
+            [
+                Feedback::Green,
+                Feedback::Yellow,
+                Feedback::Gray,
+                Feedback::Gray,
+                Feedback::Yellow
+            ]
+        );
+        assert!(parse_code_feedback("slate", "GYBB").is_err());
+        assert!(parse_code_feedback("slate", "GYBBYG").is_err());
+        assert!(parse_code_feedback("slate", "GYBBX").is_err());
+        assert!(parse_code_feedback("slate", "").is_err());
+
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let feedback = parse_code_feedback("slate", "BGBYB").unwrap();
+        game.apply_feedback("slate", &feedback).unwrap();
+        assert_eq!(game.playfield, ['-', 'L', '-', '-', '-']);
+        assert_eq!(game.excluded_positions[3], ['t']);
+        assert_eq!(game.wrong_letters, ['s', 'a', 'e']);
+    }
+
+    #[test]
+    fn test_parse_batch_line() {
+        let (guess, feedback) = parse_batch_line("guess=Slate pattern=bgByb").unwrap();
+        assert_eq!(guess, "slate");
+        assert_eq!(
+            feedback,
+            [
+                Feedback::Gray,
+                Feedback::Green,
+                Feedback::Gray,
+                Feedback::Yellow,
+                Feedback::Gray
+            ]
+        );
+        assert_eq!(
+            parse_batch_line("pattern=BGBYB guess=slate").unwrap().0,
+            "slate"
+        );
+        assert!(parse_batch_line("guess=slate").is_err());
+        assert!(parse_batch_line("guess=slate pattern=BGBY").is_err());
+        assert!(parse_batch_line("guess=slate pattern=BGBYX").is_err());
+        assert!(parse_batch_line("word=slate pattern=BGBYB").is_err());
+    }
+
+    #[test]
+    fn test_apply_batch() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let words = ["sauce", "sabre", "snake", "saute", "salsa"].map(String::from);
+        let batch = "guess=slate pattern=GBYBG\n\n# second guess\nguess=crane pattern=YBYBG\n";
+        assert_eq!(
+            apply_batch(&mut game, &words, batch.lines()).unwrap(),
+            ["sauce"]
+        );
+
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let error = apply_batch(&mut game, &words, ["guess=slate pattern=GB"]).unwrap_err();
+        assert!(error.to_string().starts_with("Line 1"));
+    }
+
+    #[test]
+    fn test_apply_pick() {
+        let words = ["batch", "hatch", "match", "latch"].map(String::from);
+        let new_game = || {
+            Game::new(
+                GameLanguage::English,
+                GameLength::Five,
+                vec!['-'; 5],
+                vec![],
+            )
+        };
+        let mut answer = new_game();
+        answer.apply_pick("hatch", None).unwrap();
+        assert_eq!(answer.playfield, ['H', 'A', 'T', 'C', 'H']);
+        assert_eq!(solve(&answer, &words), ["hatch"]);
+
+        let mut wrong = new_game();
+        let feedback = compute_feedback("hatch", "latch");
+        wrong.apply_pick("hatch", Some(&feedback)).unwrap();
+        assert_eq!(solve(&wrong, &words), ["batch", "match", "latch"]);
+    }
+
+    #[test]
+    fn test_apply_feedback() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let feedback = parse_emoji_feedback("slate", "🟩🟨⬛⬛⬛").unwrap();
+        game.apply_feedback("slate", &feedback).unwrap();
+        assert_eq!(game.playfield, ['S', '-', '-', '-', '-']);
+        assert_eq!(game.excluded_positions[1], ['l']);
+        assert_eq!(game.wrong_letters, ['a', 't', 'e']);
+
+        let words = ["sully", "spill", "salty", "lousy"].map(String::from);
+        assert_eq!(solve(&game, &words), ["sully", "spill"]);
+    }
+}
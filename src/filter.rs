@@ -1,8 +1,26 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    borrow::Cow,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    hash::BuildHasher,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
-use crate::Game;
+use crate::{
+    check_word,
+    feedback::{compute_feedback, Feedback},
+    parallel, Elimination, Game, GuessSource, LanguageProfile,
+};
 
-pub fn words_without_duplicate_letters(possible_words: &[String]) -> Vec<String> {
+#[derive(Debug, PartialEq, Eq)]
+pub struct FilterResult {
+    pub words: Vec<String>,
+    // False when the filter had nothing to go on, like when no word was left after filtering
+    // or no word had any of the letters it looks for
+    pub filtered: bool,
+}
+
+pub fn words_without_duplicate_letters(possible_words: &[String]) -> FilterResult {
     let mut words_without_duplicate_letters: Vec<String> = Vec::new();
     let mut wordhash = HashSet::new();
     'word: for word in possible_words {
@@ -13,23 +31,88 @@ pub fn words_without_duplicate_letters(possible_words: &[String]) -> Vec<String>
             }
         }
 
-        words_without_duplicate_letters.push(word.to_string());
+        words_without_duplicate_letters.push(word.clone());
     }
-    if !words_without_duplicate_letters.is_empty() {
-        println!("Filtering out words with duplicate letters...");
+    crate::debug!(
+        "{} of {} words have no duplicate letters",
+        words_without_duplicate_letters.len(),
+        possible_words.len()
+    );
+    FilterResult {
+        filtered: !words_without_duplicate_letters.is_empty(),
+        words: words_without_duplicate_letters,
     }
-    words_without_duplicate_letters
 }
 
-pub fn words_with_common_letters(possible_words: &[String], game: &Game) -> Vec<String> {
-    let common_letters: Vec<char> = match game.language {
-        crate::GameLanguage::Swedish => vec!['e', 'a', 'n', 'r', 't', 's', 'i', 'l', 'd'],
-        crate::GameLanguage::English => vec!['e', 't', 'a', 'o', 'i', 'n', 's', 'h', 'l'],
-    };
+// The suggestion filters, each applied to the words the one before it kept
+pub struct Suggestions {
+    // None when the duplicate letter filter is skipped
+    pub no_duplicates: Option<FilterResult>,
+    pub no_uncommon: FilterResult,
+    pub best_guesses: FilterResult,
+}
 
-    let mut filtered_common_letters: Vec<char> = common_letters;
-    // Don't include letters locked in place)
-    filtered_common_letters.retain(|&f| !game.playfield.contains(&f.to_ascii_uppercase()));
+// With keep_duplicates the duplicate letter filter is skipped, late in a game the answer may well
+// repeat a letter
+pub fn suggest(
+    game: &Game,
+    possible_words: &[String],
+    tie_break: TieBreak,
+    keep_duplicates: bool,
+) -> Suggestions {
+    let no_duplicates = (!keep_duplicates).then(|| words_without_duplicate_letters(possible_words));
+    let candidates = no_duplicates
+        .as_ref()
+        .map_or(possible_words, |result| result.words.as_slice());
+    let no_uncommon = words_without_uncommon_letters(candidates, game);
+    let best_guesses = words_with_common_letters(&no_uncommon.words, game, tie_break);
+    Suggestions {
+        no_duplicates,
+        no_uncommon,
+        best_guesses,
+    }
+}
+
+// How words with the same score are ordered. Alphabetical unless a seed is given, then they're
+// shuffled the same way for the same seed.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum TieBreak {
+    #[default]
+    Alphabetical,
+    Seeded(u64),
+}
+
+impl TieBreak {
+    pub fn order(self, words: &mut [String]) {
+        match self {
+            Self::Alphabetical => words.sort_unstable(),
+            Self::Seeded(seed) => {
+                words.sort_by_cached_key(|word| (seeded_hash(seed, word), word.clone()));
+            }
+        }
+    }
+}
+
+// FNV-1a of the word mixed with the seed, so the order doesn't depend on the Rust version like
+// the standard library hashers could
+fn seeded_hash(seed: u64, word: &str) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325 ^ seed;
+    for byte in word.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    // Spread the last bytes to the high bits too, similar words would sort together otherwise
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^ (hash >> 33)
+}
+
+pub fn words_with_common_letters(
+    possible_words: &[String],
+    game: &Game,
+    tie_break: TieBreak,
+) -> FilterResult {
+    let filtered_common_letters = common_letters(game);
     let mut words_with_common_letters_map: HashMap<usize, Vec<String>> = HashMap::new();
     for word in possible_words {
         let hits: Vec<&char> = filtered_common_letters
@@ -40,7 +123,7 @@ pub fn words_with_common_letters(possible_words: &[String], game: &Game) -> Vec<
             words_with_common_letters_map
                 .entry(hits.len())
                 .or_default()
-                .push(word.to_string());
+                .push(word.clone());
         }
     }
 
@@ -49,32 +132,713 @@ pub fn words_with_common_letters(possible_words: &[String], game: &Game) -> Vec<
         .max_by_key(|&(key, _)| key)
         .map(|(_, words)| words);
     words_with_most_common_letters.map_or_else(
-        || possible_words.into(),
-        |words| {
-            println!("Filtering out words with the highest amount of common letters...");
-            words
+        || FilterResult {
+            words: possible_words.into(),
+            filtered: false,
+        },
+        |mut words| {
+            crate::debug!(
+                "{} of {} words have the most common letters",
+                words.len(),
+                possible_words.len()
+            );
+            tie_break.order(&mut words);
+            FilterResult {
+                words,
+                filtered: true,
+            }
         },
     )
 }
 
-pub fn words_without_uncommon_letters(possible_words: &[String], game: &Game) -> Vec<String> {
-    let uncommon_letters: Vec<char> = match game.language {
-        crate::GameLanguage::Swedish => vec!['q', 'z', 'w', 'x', 'j', 'y'],
-        crate::GameLanguage::English => vec!['z', 'q', 'x', 'j', 'v', 'b'],
-    };
+// The most common letters of the language, except the ones already locked in place
+fn common_letters(game: &Game) -> Vec<char> {
+    let mut filtered_common_letters: Vec<char> = game.profile.common.clone();
+    // Don't include letters locked in place)
+    filtered_common_letters.retain(|&f| !game.playfield.contains(&f.to_ascii_uppercase()));
+    filtered_common_letters
+}
+
+// Most common words first, words without frequency data last
+pub fn rank_by_frequency<S: BuildHasher>(
+    possible_words: &[String],
+    frequencies: &HashMap<String, u64, S>,
+) -> Vec<String> {
+    let mut ranked = possible_words.to_vec();
+    ranked.sort_by_key(|word| Reverse(frequencies.get(word)));
+    ranked
+}
+
+// The word to play when only a few are left, the one most likely to be the answer. That's the
+// most common word when there are frequencies, otherwise the word that best tells the others
+// apart if it's wrong, then the one with the most common letters.
+pub fn final_guess<S: BuildHasher>(
+    possible_words: &[String],
+    game: &Game,
+    frequencies: &HashMap<String, u64, S>,
+) -> Option<String> {
+    if possible_words
+        .iter()
+        .any(|word| frequencies.contains_key(word))
+    {
+        return rank_by_frequency(possible_words, frequencies)
+            .into_iter()
+            .next();
+    }
+    let mut best: Option<(&String, f64, usize)> = None;
+    for word in possible_words {
+        let score = entropy(word, possible_words);
+        let common_letters = common_letter_count(word, game);
+        if best.is_none_or(|(_, best_score, best_common_letters)| {
+            score
+                .total_cmp(&best_score)
+                .then(common_letters.cmp(&best_common_letters))
+                .is_gt()
+        }) {
+            best = Some((word, score, common_letters));
+        }
+    }
+    best.map(|(word, _, _)| word.clone())
+}
+
+// Rank words by how many common letters they have and how common the word is. Both are
+// scaled to 0-1 against the best word so they weigh the same, frequency on a log scale since
+// a few words are far more common than the rest.
+#[allow(clippy::cast_precision_loss)]
+pub fn blend_frequency_with_common_letters<S: BuildHasher>(
+    possible_words: &[String],
+    game: &Game,
+    frequencies: &HashMap<String, u64, S>,
+) -> Vec<(String, f64)> {
+    let common_letters = common_letters(game);
+    let hits = |word: &String| common_letters.iter().filter(|&&c| word.contains(c)).count();
+    let frequency = |word: &String| frequencies.get(word).map_or(0.0, |&f| (f as f64).ln_1p());
+    let max_hits = possible_words.iter().map(hits).max().unwrap_or(0).max(1) as f64;
+    let max_frequency = possible_words
+        .iter()
+        .map(frequency)
+        .fold(0.0, f64::max)
+        .max(f64::MIN_POSITIVE);
+
+    let mut ranked: Vec<(&String, f64)> = possible_words
+        .iter()
+        .map(|word| {
+            let score = hits(word) as f64 / max_hits + frequency(word) / max_frequency;
+            (word, score)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+        .into_iter()
+        .map(|(word, score)| (word.clone(), score))
+        .collect()
+}
+
+// How many of the common letters of the language the word has, not counting letters locked in
+// place
+pub fn common_letter_count(word: &str, game: &Game) -> usize {
+    common_letters(game)
+        .iter()
+        .filter(|&&c| word.contains(c))
+        .count()
+}
+
+// Different vowels in the word, early guesses that cover more vowels tell more about the word
+pub fn distinct_vowel_count(word: &str, game: &Game) -> usize {
+    game.language
+        .vowels()
+        .iter()
+        .filter(|&&vowel| word.contains(vowel))
+        .count()
+}
+
+// Different letters in the word that no guess has tried yet
+pub fn fresh_letter_count(word: &str, game: &Game) -> usize {
+    game.unknown_letters()
+        .iter()
+        .filter(|&&letter| word.contains(letter))
+        .count()
+}
+
+// The percent of the different letters in the possible words that are also in the word, rounded
+pub fn letter_coverage(word: &str, possible_words: &[String]) -> usize {
+    let pool_letters: HashSet<char> = possible_words
+        .iter()
+        .flat_map(|word| word.to_lowercase().chars().collect::<Vec<char>>())
+        .collect();
+    if pool_letters.is_empty() {
+        return 0;
+    }
+    let word = word.to_lowercase();
+    let covered = pool_letters
+        .iter()
+        .filter(|&&letter| word.contains(letter))
+        .count();
+    (covered * 100 + pool_letters.len() / 2) / pool_letters.len()
+}
 
-    let mut used_uncommon_letters: Vec<char> = uncommon_letters;
-    used_uncommon_letters.retain(|&f| !game.playfield.contains(&f));
+pub fn words_without_uncommon_letters(possible_words: &[String], game: &Game) -> FilterResult {
+    let mut used_uncommon_letters: Vec<char> = game.profile.uncommon.clone();
+    used_uncommon_letters.retain(|&f| !game.is_known_letter(f));
     let mut words_without_uncommon_letters: Vec<String> = Vec::new();
     for word in possible_words {
         if !used_uncommon_letters.iter().any(|&c| word.contains(c)) {
-            words_without_uncommon_letters.push(word.to_string());
+            words_without_uncommon_letters.push(word.clone());
+        }
+    }
+    crate::debug!(
+        "{} of {} words have none of the uncommon letters {:?}",
+        words_without_uncommon_letters.len(),
+        possible_words.len(),
+        used_uncommon_letters
+    );
+    FilterResult {
+        filtered: !words_without_uncommon_letters.is_empty(),
+        words: words_without_uncommon_letters,
+    }
+}
+
+// The word whose letters occur in the most words of the list. Letters the game already knows
+// about don't count, and on a tie the first word in the list wins.
+pub fn best_opening_word(words: &[String], game: &Game) -> Option<String> {
+    let mut letter_frequencies: HashMap<char, usize> = HashMap::new();
+    for word in words {
+        for letter in distinct_letters(word) {
+            *letter_frequencies.entry(letter).or_default() += 1;
+        }
+    }
+
+    let mut best_word: Option<(&String, usize)> = None;
+    for word in words {
+        let score: usize = distinct_letters(word)
+            .iter()
+            .filter(|&&letter| {
+                !game.is_known_letter(letter) && !game.wrong_letters.contains(&letter)
+            })
+            .map(|letter| letter_frequencies[letter])
+            .sum();
+        if best_word.is_none_or(|(_, best_score)| score > best_score) {
+            best_word = Some((word, score));
+        }
+    }
+    best_word.map(|(word, _)| word.clone())
+}
+
+// The two words whose letters together occur in the most words of the list, scored like
+// best_opening_word with each letter counted once. The word that scores more on its own comes
+// first, and on a tie the pair found first in list order wins.
+pub fn best_opening_pair(words: &[String], game: &Game) -> Option<(String, String)> {
+    let mut letters: Vec<char> = Vec::new();
+    let mut letter_frequencies: Vec<usize> = Vec::new();
+    for word in words {
+        for letter in distinct_letters(word) {
+            if let Some(index) = letters.iter().position(|&c| c == letter) {
+                letter_frequencies[index] += 1;
+            } else {
+                letters.push(letter);
+                letter_frequencies.push(1);
+            }
+        }
+    }
+    // No alphabet has more than 128 letters, so each word's letters fit in a bit set
+    let mask_score = |mask: u128| -> usize {
+        (0..letters.len())
+            .filter(|&index| mask & (1 << index) != 0)
+            .map(|index| letter_frequencies[index])
+            .sum()
+    };
+
+    // Words with the same letters make the same pairs, only the first one is kept
+    let mut seen = HashSet::new();
+    let mut candidates: Vec<(&String, u128, usize)> = Vec::new();
+    for word in words {
+        let mask = distinct_letters(word)
+            .iter()
+            .filter(|&&letter| {
+                !game.is_known_letter(letter) && !game.wrong_letters.contains(&letter)
+            })
+            .filter_map(|letter| letters.iter().position(|c| c == letter))
+            .fold(0, |mask, index| mask | (1 << index));
+        if seen.insert(mask) {
+            candidates.push((word, mask, mask_score(mask)));
+        }
+    }
+    candidates.sort_by_key(|&(_, _, score)| Reverse(score));
+
+    // A pair never scores more than its words on their own, so the search stops once that sum
+    // can't beat the best pair
+    let mut best_pair: Option<(&String, &String, usize)> = None;
+    for (index, &(first, first_mask, first_score)) in candidates.iter().enumerate() {
+        let best_score = best_pair.map_or(0, |(_, _, score)| score);
+        if candidates
+            .get(index + 1)
+            .is_none_or(|&(_, _, score)| first_score + score <= best_score)
+        {
+            break;
+        }
+        for &(second, second_mask, second_score) in &candidates[index + 1..] {
+            let best_score = best_pair.map_or(0, |(_, _, score)| score);
+            if first_score + second_score <= best_score {
+                break;
+            }
+            let score = first_score + second_score - mask_score(first_mask & second_mask);
+            if score > best_score {
+                best_pair = Some((first, second, score));
+            }
+        }
+    }
+    best_pair.map(|(first, second, _)| (first.clone(), second.clone()))
+}
+
+fn distinct_letters(word: &str) -> HashSet<char> {
+    word.to_lowercase().chars().collect()
+}
+
+// Guesses allowed in hard mode. The possible words always are, but a probe word that isn't a
+// possible answer must still keep greens in place, use all yellows and skip gray letters.
+pub fn hard_mode_candidates(guesses: &[String], game: &Game) -> Vec<String> {
+    if !game.hard_mode {
+        return guesses.to_vec();
+    }
+    guesses
+        .iter()
+        .filter(|guess| {
+            let guess: Vec<char> = guess.to_lowercase().chars().collect();
+            guess.len() == game.playfield.len()
+                && game.playfield.iter().zip(&guess).all(|(&slot, &letter)| {
+                    !slot.is_uppercase() || slot.to_lowercase().eq(letter.to_lowercase())
+                })
+                && game
+                    .excluded_positions
+                    .iter()
+                    .flatten()
+                    .all(|letter| guess.contains(letter))
+                && guess.iter().all(|&letter| {
+                    !game.wrong_letters.contains(&letter) || game.is_known_letter(letter)
+                })
+        })
+        .cloned()
+        .collect()
+}
+
+// The words to rank as guesses, the possible words or all allowed guesses that can be played
+pub fn guess_pool<'a>(
+    game: &Game,
+    possible_words: &'a [String],
+    allowed_guesses: &'a [String],
+) -> Cow<'a, [String]> {
+    match game.guess_source {
+        GuessSource::PossibleWords => Cow::Borrowed(possible_words),
+        GuessSource::AllowedGuesses => Cow::Owned(hard_mode_candidates(allowed_guesses, game)),
+    }
+}
+
+// A way to score guesses, the guesses with the highest scores are suggested. Words are the
+// possible words left.
+pub trait ScoringStrategy: Sync {
+    fn score(&self, word: &str, words: &[String], game: &Game) -> f64;
+}
+
+// Number of common letters of the language in the guess
+pub struct CommonLetters;
+
+// Number of common letters, with more distinct vowels scoring higher among guesses with as many
+// common letters
+pub struct CommonLettersAndVowels;
+
+// Number of different letters that no guess has tried yet
+pub struct FreshLetters;
+
+// Expected information in bits
+pub struct Entropy;
+
+// The worst case number of words left, negated so the smallest worst case scores highest
+pub struct Minimax;
+
+impl ScoringStrategy for CommonLetters {
+    #[allow(clippy::cast_precision_loss)]
+    fn score(&self, word: &str, _words: &[String], game: &Game) -> f64 {
+        common_letter_count(word, game) as f64
+    }
+}
+
+impl ScoringStrategy for CommonLettersAndVowels {
+    #[allow(clippy::cast_precision_loss)]
+    fn score(&self, word: &str, _words: &[String], game: &Game) -> f64 {
+        // A word can't have more distinct vowels than letters, so the vowels never outweigh a
+        // common letter
+        let vowels = distinct_vowel_count(word, game) as f64 / (game.length as usize + 1) as f64;
+        common_letter_count(word, game) as f64 + vowels
+    }
+}
+
+impl ScoringStrategy for FreshLetters {
+    #[allow(clippy::cast_precision_loss)]
+    fn score(&self, word: &str, _words: &[String], game: &Game) -> f64 {
+        fresh_letter_count(word, game) as f64
+    }
+}
+
+impl ScoringStrategy for Entropy {
+    fn score(&self, word: &str, words: &[String], _game: &Game) -> f64 {
+        entropy(word, words)
+    }
+}
+
+impl ScoringStrategy for Minimax {
+    #[allow(clippy::cast_precision_loss)]
+    fn score(&self, word: &str, words: &[String], _game: &Game) -> f64 {
+        -(worst_case(word, words) as f64)
+    }
+}
+
+// The guesses with the highest score first, guesses with the same score keep their order.
+// Progress is called like for rank_by_entropy_with_progress.
+pub fn rank_with_strategy<S, P>(
+    strategy: &S,
+    possible_words: &[String],
+    guess_pool: &[String],
+    game: &Game,
+    progress: P,
+) -> Vec<(String, f64)>
+where
+    S: ScoringStrategy + ?Sized,
+    P: Fn(usize, usize) + Sync,
+{
+    rank_by(
+        guess_pool,
+        progress,
+        |guess| strategy.score(guess, possible_words, game),
+        |a, b| b.total_cmp(a),
+    )
+}
+
+// Score every guess of the pool on the threads and sort them by the scores, guesses with the
+// same score keep their order
+fn rank_by<T, P, F, C>(guess_pool: &[String], progress: P, score: F, compare: C) -> Vec<(String, T)>
+where
+    T: Send,
+    P: Fn(usize, usize) + Sync,
+    F: Fn(&str) -> T + Sync,
+    C: Fn(&T, &T) -> std::cmp::Ordering,
+{
+    let scored = AtomicUsize::new(0);
+    // Scoring a guess goes through the whole list, so even short lists are worth splitting
+    let mut ranked: Vec<(String, T)> = parallel::map(guess_pool, 32, |guess| {
+        let score = score(guess);
+        progress(scored.fetch_add(1, Ordering::Relaxed) + 1, guess_pool.len());
+        (guess.clone(), score)
+    });
+    ranked.sort_by(|a, b| compare(&a.1, &b.1));
+    ranked
+}
+
+// How many bits less information the guess is expected to give than the best guess of the pool,
+// with that guess. A guess at least as good as every guess in the pool has no regret and is its
+// own best guess. Progress is called like for rank_by_entropy_with_progress.
+pub fn regret<P>(
+    guess: &str,
+    possible_words: &[String],
+    guess_pool: &[String],
+    progress: P,
+) -> (String, f64)
+where
+    P: Fn(usize, usize) + Sync,
+{
+    let guess = guess.to_lowercase();
+    let chosen = entropy(&guess, possible_words);
+    rank_by_entropy_with_progress(possible_words, guess_pool, progress)
+        .into_iter()
+        .next()
+        .filter(|&(_, best)| best > chosen)
+        .map_or((guess, 0.0), |(best_guess, best)| {
+            (best_guess, best - chosen)
+        })
+}
+
+pub fn rank_by_entropy(possible_words: &[String]) -> Vec<(String, f64)> {
+    rank_by_entropy_with_progress(possible_words, possible_words, |_, _| {})
+}
+
+// Progress is called with the number of guesses scored so far and the number of guesses, from
+// any of the threads scoring them
+pub fn rank_by_entropy_with_progress<P>(
+    possible_words: &[String],
+    guess_pool: &[String],
+    progress: P,
+) -> Vec<(String, f64)>
+where
+    P: Fn(usize, usize) + Sync,
+{
+    rank_by(
+        guess_pool,
+        progress,
+        |guess| entropy(guess, possible_words),
+        |a, b| b.total_cmp(a),
+    )
+}
+
+// Guesses for several boards played at once, like Dordle, scored by the summed expected
+// information on all boards. Any possible word on any board can be guessed.
+pub fn rank_for_boards(boards: &[&[String]]) -> Vec<(String, f64)> {
+    let mut seen = HashSet::new();
+    let guess_pool: Vec<String> = boards
+        .iter()
+        .flat_map(|words| words.iter())
+        .filter(|word| seen.insert(word.as_str()))
+        .cloned()
+        .collect();
+    rank_by(
+        &guess_pool,
+        |_, _| {},
+        |guess| boards.iter().map(|words| entropy(guess, words)).sum(),
+        |a: &f64, b| b.total_cmp(a),
+    )
+}
+
+// Guesses with the smallest worst case first, the size of the largest group of possible words
+// that could be left after the feedback of the guess
+pub fn rank_by_minimax(possible_words: &[String], guess_pool: &[String]) -> Vec<(String, usize)> {
+    rank_by_minimax_with_progress(possible_words, guess_pool, |_, _| {})
+}
+
+pub fn rank_by_minimax_with_progress<P>(
+    possible_words: &[String],
+    guess_pool: &[String],
+    progress: P,
+) -> Vec<(String, usize)>
+where
+    P: Fn(usize, usize) + Sync,
+{
+    rank_by(
+        guess_pool,
+        progress,
+        |guess| worst_case(guess, possible_words),
+        Ord::cmp,
+    )
+}
+
+fn worst_case(guess: &str, possible_words: &[String]) -> usize {
+    partition_sizes(guess, possible_words)
+        .last()
+        .copied()
+        .unwrap_or(0)
+}
+
+// The clue that removed a word from the possible words, or None if it fits all of them
+pub fn explain_elimination(word: &str, game: &Game) -> Option<String> {
+    let reason = match check_word(word, game).err()? {
+        Elimination::Length(length) => {
+            format!("it has {} letters, not {}", length, game.length as usize)
+        }
+        Elimination::Banned => "the word is banned".to_string(),
+        Elimination::NotGreen {
+            letter,
+            position,
+            green,
+        } => format!(
+            "'{}' is green at position {} but the word has '{}' there",
+            green, position, letter
+        ),
+        Elimination::YellowInSlot { letter, position } => format!(
+            "'{}' is yellow at position {} so it can't be there",
+            letter, position
+        ),
+        Elimination::YellowMissing(letter) => {
+            format!("'{}' is yellow but not in the word", letter)
+        }
+        Elimination::Misplaced { letter, position } => format!(
+            "'{}' was yellow at position {} in an earlier guess so it can't be there",
+            letter, position
+        ),
+        Elimination::Absent { letter, position } => format!(
+            "'{}' is marked absent but appears at position {}",
+            letter, position
+        ),
+        Elimination::NotExactly {
+            letter,
+            count,
+            exact,
+        } => format!(
+            "'{}' appears {} but is known to appear exactly {}",
+            letter,
+            times(count),
+            times(exact)
+        ),
+        Elimination::TooFew { letter, count, min } => format!(
+            "'{}' appears {} but is known to appear at least {}",
+            letter,
+            times(count),
+            times(min)
+        ),
+        Elimination::TooMany { letter, count, max } => format!(
+            "'{}' appears {} but can appear at most {}",
+            letter,
+            times(count),
+            times(max)
+        ),
+        Elimination::KnownMissing(letter) => {
+            format!("'{}' is known to be in the word but isn't", letter)
+        }
+    };
+    Some(format!("eliminated: {}", reason))
+}
+
+fn times(count: usize) -> String {
+    match count {
+        1 => "once".to_string(),
+        count => format!("{} times", count),
+    }
+}
+
+// Words with all the letters anywhere in them, in any case. Doesn't use any clues from the game.
+pub fn must_contain(words: &[String], letters: &[char]) -> Vec<String> {
+    words
+        .iter()
+        .filter(|word| contains_all(&word.to_lowercase(), letters))
+        .cloned()
+        .collect()
+}
+
+fn contains_all(word: &str, letters: &[char]) -> bool {
+    letters
+        .iter()
+        .all(|&letter| word.contains(crate::to_lowercase(letter)))
+}
+
+// Words matching a pattern like "s_a_e", where _ is any letter, that also contain all the
+// required letters. Doesn't use any clues from the game.
+pub fn pattern_search(words: &[String], pattern: &str, required_letters: &[char]) -> Vec<String> {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    words
+        .iter()
+        .filter(|word| {
+            let word = word.to_lowercase();
+            word.chars().count() == pattern.len()
+                && word
+                    .chars()
+                    .zip(&pattern)
+                    .all(|(letter, &slot)| slot == '_' || letter == slot)
+                && contains_all(&word, required_letters)
+        })
+        .cloned()
+        .collect()
+}
+
+// The largest group of at least three words that only differ in one slot, with the pattern they
+// share, like "_atch" for batch, catch and latch. Each of them can take a guess to rule out.
+// Ties go to the pattern that sorts first.
+pub fn largest_cluster(words: &[String]) -> Option<(String, Vec<String>)> {
+    let mut clusters: HashMap<String, Vec<String>> = HashMap::new();
+    for word in words {
+        let letters: Vec<char> = word.to_lowercase().chars().collect();
+        for slot in 0..letters.len() {
+            let pattern = letters
+                .iter()
+                .enumerate()
+                .map(|(index, &letter)| if index == slot { '_' } else { letter })
+                .collect();
+            clusters.entry(pattern).or_default().push(word.clone());
+        }
+    }
+    clusters
+        .into_iter()
+        .filter(|(_, words)| words.len() >= 3)
+        .max_by(|(a_pattern, a), (b_pattern, b)| {
+            a.len().cmp(&b.len()).then_with(|| b_pattern.cmp(a_pattern))
+        })
+}
+
+// How many times each letter occurs in the words, the most common first and ties in
+// alphabetical order
+pub fn compute_letter_frequencies<W: AsRef<str>>(words: &[W]) -> Vec<(char, usize)> {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for word in words {
+        for letter in word.as_ref().to_lowercase().chars() {
+            *counts.entry(letter).or_default() += 1;
         }
     }
-    if !words_without_uncommon_letters.is_empty() {
-        println!("Filtering out possible words with uncommon letters...");
+    let mut frequencies: Vec<(char, usize)> = counts.into_iter().collect();
+    frequencies.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    frequencies
+}
+
+// The most and least common letters of a word list, for word lists of any language
+pub fn profile_from_words<W: AsRef<str>>(
+    words: &[W],
+    common_count: usize,
+    uncommon_count: usize,
+) -> LanguageProfile {
+    let letters: Vec<char> = compute_letter_frequencies(words)
+        .into_iter()
+        .map(|(letter, _)| letter)
+        .collect();
+    let common: Vec<char> = letters.iter().take(common_count).copied().collect();
+    let uncommon = letters
+        .iter()
+        .rev()
+        .take(uncommon_count)
+        .filter(|letter| !common.contains(letter))
+        .copied()
+        .collect();
+    LanguageProfile { common, uncommon }
+}
+
+// How many of the words have each letter in each slot
+pub fn positional_frequencies(words: &[String], length: usize) -> Vec<HashMap<char, usize>> {
+    let mut frequencies = vec![HashMap::new(); length];
+    for word in words {
+        for (slot, letter) in word.to_lowercase().chars().enumerate().take(length) {
+            *frequencies[slot].entry(letter).or_default() += 1;
+        }
     }
-    words_without_uncommon_letters
+    frequencies
+}
+
+// Number of possible words left for each feedback the guess could get, keyed by patterns like
+// "GYBBB" with G for correct slot, Y for wrong slot and B for not in word
+pub fn outcome_distribution(guess: &str, possible_words: &[String]) -> HashMap<String, usize> {
+    let guess = guess.to_lowercase();
+    let mut distribution: HashMap<String, usize> = HashMap::new();
+    for answer in possible_words {
+        let pattern = compute_feedback(&guess, &answer.to_lowercase())
+            .into_iter()
+            .map(|feedback| match feedback {
+                Feedback::Green => 'G',
+                Feedback::Yellow => 'Y',
+                Feedback::Gray => 'B',
+            })
+            .collect();
+        *distribution.entry(pattern).or_default() += 1;
+    }
+    distribution
+}
+
+// Expected information in bits from the feedback of a guess
+#[allow(clippy::cast_precision_loss)]
+fn entropy(guess: &str, possible_words: &[String]) -> f64 {
+    let total = possible_words.len() as f64;
+    // Sum in a fixed order so words with equal partitions get exactly the same entropy
+    partition_sizes(guess, possible_words)
+        .into_iter()
+        .map(|count| {
+            let probability = count as f64 / total;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+// Number of possible words giving each feedback pattern for the guess, smallest first
+fn partition_sizes(guess: &str, possible_words: &[String]) -> Vec<usize> {
+    let mut partitions: HashMap<Vec<Feedback>, usize> = HashMap::new();
+    for answer in possible_words {
+        *partitions
+            .entry(compute_feedback(guess, answer))
+            .or_default() += 1;
+    }
+    let mut partition_sizes: Vec<usize> = partitions.into_values().collect();
+    partition_sizes.sort_unstable();
+    partition_sizes
 }
 
 #[cfg(test)]
@@ -85,19 +849,642 @@ mod tests {
     fn test_words_without_duplicate_letters() {
         let words = vec!["spade".to_string(), "ribba".to_string()];
         let returned = words_without_duplicate_letters(&words);
-        assert_eq!(returned, ["spade"]);
+        assert_eq!(returned.words, ["spade"]);
+        assert!(returned.filtered);
+
+        let returned = words_without_duplicate_letters(&["ribba".to_string()]);
+        assert!(returned.words.is_empty());
+        assert!(!returned.filtered);
     }
 
     #[test]
     fn test_words_with_common_letters() {
-        let game: &Game = &Game {
-            language: crate::GameLanguage::Swedish,
-            length: crate::GameLength::Five,
-            playfield: vec!['a', '-', 'k', '-', '-'],
-            wrong_letters: vec!['g'],
-        };
+        let game: &Game = &Game::new(
+            crate::GameLanguage::Swedish,
+            crate::GameLength::Five,
+            vec!['a', '-', 'k', '-', '-'],
+            vec!['g'],
+        );
         let words = &["aktie".to_string()];
-        let returned = words_with_common_letters(words, game);
-        assert_eq!(returned, ["aktie"]);
+        let returned = words_with_common_letters(words, game, TieBreak::Alphabetical);
+        assert_eq!(returned.words, ["aktie"]);
+        assert!(returned.filtered);
+    }
+
+    #[test]
+    fn test_tie_break() {
+        let game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        // All of them have the same number of common letters
+        let words = ["tears", "rates", "aster", "stare", "tares"].map(String::from);
+        let mut reversed = words.clone();
+        reversed.reverse();
+        let alphabetical = words_with_common_letters(&words, &game, TieBreak::Alphabetical);
+        assert_eq!(
+            alphabetical.words,
+            ["aster", "rates", "stare", "tares", "tears"]
+        );
+        assert_eq!(
+            words_with_common_letters(&reversed, &game, TieBreak::Alphabetical),
+            alphabetical
+        );
+
+        let seeded = words_with_common_letters(&words, &game, TieBreak::Seeded(7)).words;
+        for _ in 0..3 {
+            assert_eq!(
+                words_with_common_letters(&reversed, &game, TieBreak::Seeded(7)).words,
+                seeded
+            );
+        }
+        let mut sorted = seeded;
+        sorted.sort_unstable();
+        assert_eq!(sorted, alphabetical.words);
+        assert_ne!(seeded_hash(7, "tears"), seeded_hash(8, "tears"));
+    }
+
+    #[test]
+    fn test_rank_by_entropy() {
+        let words = ["crane", "slate", "shale", "stale", "crate", "trace"].map(String::from);
+        let returned = rank_by_entropy(&words);
+        let order: Vec<&str> = returned.iter().map(|(word, _)| word.as_str()).collect();
+        assert_eq!(
+            order,
+            ["slate", "crate", "stale", "trace", "crane", "shale"]
+        );
+        assert!((returned[0].1 - 6f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_guess_pool_suggests_allowed_guesses() {
+        let answers = [
+            "paddy", "giddy", "biddy", "caddy", "buddy", "toddy", "ruddy", "daddy",
+        ]
+        .map(String::from);
+        let allowed_guesses: Vec<String> = answers
+            .iter()
+            .cloned()
+            .chain(["birth", "crump"].map(String::from))
+            .collect();
+        let mut game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            "---DY".chars().collect(),
+            vec![],
+        );
+        assert_eq!(guess_pool(&game, &answers, &allowed_guesses), &answers[..]);
+
+        game.guess_source = GuessSource::AllowedGuesses;
+        let pool = guess_pool(&game, &answers, &allowed_guesses);
+        let ranked = rank_by_entropy_with_progress(&answers, &pool, |_, _| {});
+        // Guessing a word that can't be the answer tells more about the D:s than any answer
+        assert!(!answers.contains(&ranked[0].0));
+        assert!(ranked[0].1 > entropy(&answers[0], &answers));
+    }
+
+    #[test]
+    fn test_rank_with_progress() {
+        let words = ["crane", "slate", "shale", "stale", "crate", "trace"].map(String::from);
+        assert_eq!(
+            rank_by_entropy_with_progress(&words, &words, |_, _| {}),
+            rank_by_entropy(&words)
+        );
+        assert_eq!(
+            rank_by_minimax_with_progress(&words, &words, |_, _| {}),
+            rank_by_minimax(&words, &words)
+        );
+
+        let calls = AtomicUsize::new(0);
+        rank_by_entropy_with_progress(&words, &words, |scored, total| {
+            assert!(scored >= 1 && scored <= total);
+            assert_eq!(total, 6);
+            calls.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(calls.into_inner(), 6);
+    }
+
+    #[test]
+    fn test_explain_elimination() {
+        let game = |playfield: &str, wrong: &str| {
+            let mut game = Game::new(
+                crate::GameLanguage::English,
+                crate::GameLength::Five,
+                playfield.chars().collect(),
+                wrong.chars().collect(),
+            );
+            game.update_letter_counts();
+            game
+        };
+        let explain = |word, game: &Game| explain_elimination(word, game);
+
+        assert_eq!(explain("slate", &game("SL---", "")), None);
+        assert_eq!(
+            explain("cat", &game("-----", "")).as_deref(),
+            Some("eliminated: it has 3 letters, not 5")
+        );
+        let mut banned = game("-----", "");
+        banned.banned_words.insert("slate".to_string());
+        assert_eq!(
+            explain("Slate", &banned).as_deref(),
+            Some("eliminated: the word is banned")
+        );
+        assert_eq!(
+            explain("crane", &game("S----", "")).as_deref(),
+            Some("eliminated: 's' is green at position 1 but the word has 'c' there")
+        );
+        assert_eq!(
+            explain("slate", &game("--a--", "")).as_deref(),
+            Some("eliminated: 'a' is yellow at position 3 so it can't be there")
+        );
+        assert_eq!(
+            explain("crime", &game("--a--", "")).as_deref(),
+            Some("eliminated: 'a' is yellow but not in the word")
+        );
+        let mut misplaced = game("-----", "");
+        misplaced.excluded_positions[3].push('t');
+        assert_eq!(
+            explain("slate", &misplaced).as_deref(),
+            Some(
+                "eliminated: 't' was yellow at position 4 in an earlier guess so it can't be there"
+            )
+        );
+        assert_eq!(
+            explain("crane", &misplaced).as_deref(),
+            Some("eliminated: 't' is known to be in the word but isn't")
+        );
+        assert_eq!(
+            explain("crane", &game("-----", "r")).as_deref(),
+            Some("eliminated: 'r' is marked absent but appears at position 2")
+        );
+        let mut repeated = game("-----", "");
+        repeated.require_letters("ee");
+        assert_eq!(
+            explain("slate", &repeated).as_deref(),
+            Some("eliminated: 'e' appears once but is known to appear at least 2 times")
+        );
+        assert_eq!(
+            explain("eerie", &game("E----", "e")).as_deref(),
+            Some("eliminated: 'e' appears 3 times but is known to appear exactly once")
+        );
+    }
+
+    #[test]
+    fn test_pattern_search() {
+        let words = ["slate", "shale", "stale", "snail", "spine", "shake"].map(String::from);
+        assert_eq!(
+            pattern_search(&words, "s_a_e", &[]),
+            ["slate", "shale", "stale", "shake"]
+        );
+        assert_eq!(pattern_search(&words, "S_A_E", &['h', 'L']), ["shale"]);
+        assert_eq!(pattern_search(&words, "_____", &['i']), ["snail", "spine"]);
+        assert!(pattern_search(&words, "s_a", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_largest_cluster() {
+        let words = [
+            "batch", "slate", "catch", "latch", "stale", "match", "patch", "watch", "state",
+        ]
+        .map(String::from);
+        let (pattern, cluster) = largest_cluster(&words).unwrap();
+        assert_eq!(pattern, "_atch");
+        assert_eq!(
+            cluster,
+            ["batch", "catch", "latch", "match", "patch", "watch"]
+        );
+        // slate, stale and state don't share four letters in place
+        assert!(largest_cluster(&words[..2]).is_none());
+        assert!(largest_cluster(&["slate", "stale", "state"].map(String::from)).is_none());
+    }
+
+    #[test]
+    fn test_must_contain() {
+        let words = ["slate", "crisp", "eager", "Ocean", "irate"].map(String::from);
+        assert_eq!(
+            must_contain(&words, &['a', 'e']),
+            ["slate", "eager", "Ocean", "irate"]
+        );
+        assert_eq!(must_contain(&words, &['E', 'r', 'i']), ["irate"]);
+        assert_eq!(must_contain(&words, &[]), words);
+        assert!(must_contain(&words, &['z']).is_empty());
+    }
+
+    #[test]
+    fn test_positional_frequencies() {
+        let words = ["slate", "stale", "crate", "grate", "crane"].map(String::from);
+        let frequencies = positional_frequencies(&words, 5);
+        assert_eq!(frequencies.len(), 5);
+        assert_eq!(
+            frequencies[1],
+            HashMap::from([('l', 1), ('t', 1), ('r', 3)])
+        );
+        assert_eq!(frequencies[2], HashMap::from([('a', 5)]));
+    }
+
+    #[test]
+    fn test_outcome_distribution() {
+        let words = ["slate", "stale", "crate", "grate", "crane"].map(String::from);
+        let distribution = outcome_distribution("SLATE", &words);
+        assert_eq!(distribution.len(), 4);
+        assert_eq!(distribution["GGGGG"], 1);
+        assert_eq!(distribution["GYGYG"], 1);
+        assert_eq!(distribution["BBGGG"], 2);
+        assert_eq!(distribution["BBGBG"], 1);
+    }
+
+    #[test]
+    fn test_rank_for_boards() {
+        let first = ["slate", "stale"].map(String::from);
+        let second = ["crane", "crate", "grate"].map(String::from);
+        let ranked = rank_for_boards(&[&first, &second]);
+        assert_eq!(ranked.len(), 5);
+        // crate and grate split the second board completely and still tell slate from stale
+        assert_eq!(ranked[0].0, "crate");
+        assert_eq!(ranked[1].0, "grate");
+        assert!(ranked[1].1 > ranked[2].1);
+    }
+
+    #[test]
+    fn test_rank_by_minimax() {
+        let answers = [
+            "paddy", "giddy", "biddy", "caddy", "buddy", "toddy", "ruddy", "daddy", "teddy",
+        ]
+        .map(String::from);
+        let pool: Vec<String> = answers
+            .iter()
+            .cloned()
+            .chain(["birth", "antic"].map(String::from))
+            .collect();
+
+        // birth splits the answers into more groups, but antic never leaves more than two
+        let by_minimax = rank_by_minimax(&answers, &pool);
+        assert_eq!(by_minimax[0], ("antic".to_string(), 2));
+        assert_eq!(by_minimax[1], ("birth".to_string(), 3));
+        let best_by_entropy = pool
+            .iter()
+            .max_by(|a, b| entropy(a, &answers).total_cmp(&entropy(b, &answers)))
+            .unwrap();
+        assert_eq!(best_by_entropy, "birth");
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn test_rank_with_strategy() {
+        struct Longest;
+        impl ScoringStrategy for Longest {
+            fn score(&self, word: &str, _words: &[String], _game: &Game) -> f64 {
+                word.chars().count() as f64
+            }
+        }
+
+        let game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let words = ["ab", "abcde", "abc", "abcd", "xyz"].map(String::from);
+        let ranked: Vec<String> = rank_with_strategy(&Longest, &words, &words, &game, |_, _| {})
+            .into_iter()
+            .map(|(word, _)| word)
+            .collect();
+        assert_eq!(ranked, ["abcde", "abcd", "abc", "xyz", "ab"]);
+
+        let answers: Vec<String> =
+            crate::wordlist::embedded_words(crate::GameLanguage::English, crate::GameLength::Five)
+                .unwrap()
+                .lines()
+                .take(300)
+                .map(String::from)
+                .collect();
+        let by_entropy = rank_with_strategy(&Entropy, &answers, &answers, &game, |_, _| {});
+        assert_eq!(by_entropy, rank_by_entropy(&answers));
+        let by_minimax: Vec<(String, f64)> = rank_by_minimax(&answers, &answers)
+            .into_iter()
+            .map(|(word, worst_case)| (word, -(worst_case as f64)))
+            .collect();
+        assert_eq!(
+            rank_with_strategy(&Minimax, &answers, &answers, &game, |_, _| {}),
+            by_minimax
+        );
+        let strategy: &dyn ScoringStrategy = &CommonLetters;
+        let by_common = rank_with_strategy(strategy, &answers, &answers, &game, |_, _| {});
+        assert!(by_common
+            .windows(2)
+            .all(|pair| common_letter_count(&pair[0].0, &game)
+                >= common_letter_count(&pair[1].0, &game)));
+    }
+
+    #[test]
+    fn test_rank_by_vowels() {
+        let mut game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        game.profile.common = vec!['s', 't'];
+        // All have S and T, only the number of different vowels differ
+        let words = ["stuff", "skint", "saute", "stoat", "trust"].map(String::from);
+        let ranked: Vec<String> =
+            rank_with_strategy(&CommonLettersAndVowels, &words, &words, &game, |_, _| {})
+                .into_iter()
+                .map(|(word, _)| word)
+                .collect();
+        assert_eq!(ranked, ["saute", "stoat", "stuff", "skint", "trust"]);
+        assert_eq!(distinct_vowel_count("saute", &game), 3);
+        assert_eq!(distinct_vowel_count("stoat", &game), 2);
+        // Another common letter outweighs every vowel
+        game.profile.common.push('r');
+        assert!(
+            CommonLettersAndVowels.score("trust", &words, &game)
+                > CommonLettersAndVowels.score("saute", &words, &game)
+        );
+
+        game.language = crate::GameLanguage::Swedish;
+        assert_eq!(distinct_vowel_count("åtöka", &game), 3);
+    }
+
+    #[test]
+    fn test_letter_coverage() {
+        // The pool has the letters a, c, e, h, l, m, s, t
+        let words = ["latch", "match", "Slate"].map(String::from);
+        assert_eq!(letter_coverage("slate", &words), 63);
+        assert_eq!(letter_coverage("MATCH", &words), 63);
+        assert_eq!(letter_coverage("moody", &words), 13);
+        assert_eq!(letter_coverage("slate", &[]), 0);
+    }
+
+    #[test]
+    fn test_rank_by_fresh_letters() {
+        let mut game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        game.apply_feedback("slate", &[Feedback::Gray; 5]).unwrap();
+        assert_eq!(game.tried_letters(), ['a', 'e', 'l', 's', 't']);
+
+        let words = ["stale", "crone", "pious", "round", "doubt"].map(String::from);
+        let ranked = rank_with_strategy(&FreshLetters, &words, &words, &game, |_, _| {});
+        assert_eq!(ranked[0], ("round".to_string(), 5.0));
+        assert_eq!(fresh_letter_count("crone", &game), 4);
+        assert_eq!(fresh_letter_count("stale", &game), 0);
+        assert_eq!(ranked.last().unwrap().0, "stale");
+    }
+
+    #[test]
+    fn test_suggest_keep_duplicates() {
+        let game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            vec!['-', '-', 'R', '-', '-'],
+            vec![],
+        );
+        let words = ["error", "carry", "array"].map(String::from);
+        let suggestions = suggest(&game, &words, TieBreak::Alphabetical, false);
+        assert_eq!(
+            suggestions.no_duplicates,
+            Some(FilterResult {
+                words: vec![],
+                filtered: false
+            })
+        );
+        assert!(suggestions.best_guesses.words.is_empty());
+
+        let suggestions = suggest(&game, &words, TieBreak::Alphabetical, true);
+        assert_eq!(suggestions.no_duplicates, None);
+        assert!(suggestions
+            .best_guesses
+            .words
+            .contains(&"error".to_string()));
+    }
+
+    #[test]
+    fn test_regret() {
+        let words = ["batch", "hatch", "match", "latch"].map(String::from);
+        // BATCH only tells BATCH from the rest, 1/4 * 2 + 3/4 * log2(4/3) bits
+        let batch_bits = 0.75_f64.mul_add((4.0_f64 / 3.0).log2(), 0.5);
+        assert!((entropy("batch", &words) - batch_bits).abs() < 1e-9);
+        // BHMLQ gives each word its own feedback, 2 bits
+        assert!((entropy("bhmlq", &words) - 2.0).abs() < 1e-9);
+
+        let mut pool = words.to_vec();
+        pool.push("bhmlq".to_string());
+        let (best_guess, bits) = regret("BATCH", &words, &pool, |_, _| {});
+        assert_eq!(best_guess, "bhmlq");
+        assert!((bits - (2.0 - batch_bits)).abs() < 1e-9);
+        assert_eq!(
+            regret("bhmlq", &words, &pool, |_, _| {}),
+            ("bhmlq".to_string(), 0.0)
+        );
+        // No guess in the pool is better than another possible word
+        assert_eq!(
+            regret("latch", &words, &words, |_, _| {}),
+            ("latch".to_string(), 0.0)
+        );
+    }
+
+    #[test]
+    fn test_rank_by_entropy_parallel_matches_sequential() {
+        let words: Vec<String> =
+            crate::wordlist::embedded_words(crate::GameLanguage::English, crate::GameLength::Five)
+                .unwrap()
+                .lines()
+                .filter(|word| word.starts_with('s'))
+                .map(String::from)
+                .collect();
+
+        let parallel = rank_by_entropy(&words);
+        let mut sequential: Vec<(String, f64)> = words
+            .iter()
+            .map(|guess| (guess.clone(), entropy(guess, &words)))
+            .collect();
+        sequential.sort_by(|a, b| b.1.total_cmp(&a.1));
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn test_best_opening_word() {
+        for (language, length, opener) in [
+            (
+                crate::GameLanguage::English,
+                crate::GameLength::Five,
+                "irate",
+            ),
+            (
+                crate::GameLanguage::Swedish,
+                crate::GameLength::Five,
+                "karst",
+            ),
+            (
+                crate::GameLanguage::Swedish,
+                crate::GameLength::Six,
+                "strila",
+            ),
+            (
+                crate::GameLanguage::German,
+                crate::GameLength::Five,
+                "alter",
+            ),
+        ] {
+            let game = Game::new(language, length, vec!['-'; length as usize], vec![]);
+            let words = crate::wordlist::read_file(&game, None).unwrap();
+            assert_eq!(best_opening_word(&words, &game).as_deref(), Some(opener));
+        }
+        let game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        assert_eq!(best_opening_word(&[], &game), None);
+    }
+
+    #[test]
+    fn test_best_opening_pair() {
+        let game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let words = [
+            "slate", "crony", "stale", "crate", "pious", "lyric", "handy", "young", "caste",
+            "moist",
+        ]
+        .map(String::from);
+        let frequencies =
+            |letter: &char| words.iter().filter(|word| word.contains(*letter)).count();
+        let pair_score = |first: &str, second: &str| -> usize {
+            distinct_letters(&format!("{}{}", first, second))
+                .iter()
+                .map(frequencies)
+                .sum()
+        };
+        let (first, second) = best_opening_pair(&words, &game).unwrap();
+        assert_eq!((first.as_str(), second.as_str()), ("slate", "crony"));
+        let best_score = pair_score(&first, &second);
+        for a in &words {
+            for b in &words {
+                assert!(pair_score(a, b) <= best_score, "{} + {}", a, b);
+            }
+        }
+
+        assert_eq!(best_opening_pair(&words[..1], &game), None);
+        let words = crate::wordlist::read_file(&game, None).unwrap();
+        assert!(best_opening_pair(&words, &game).is_some());
+    }
+
+    #[test]
+    fn test_custom_language_profile() {
+        let mut game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let words = ["jumbo", "fizzy", "slate"].map(String::from);
+        assert_eq!(
+            words_without_uncommon_letters(&words, &game).words,
+            ["slate"]
+        );
+        assert_eq!(
+            words_with_common_letters(&words, &game, TieBreak::Alphabetical).words,
+            ["slate"]
+        );
+
+        game.profile = LanguageProfile {
+            common: vec!['u', 'm', 'o'],
+            uncommon: vec!['s'],
+        };
+        assert_eq!(
+            words_without_uncommon_letters(&words, &game).words,
+            ["jumbo", "fizzy"]
+        );
+        assert_eq!(
+            words_with_common_letters(&words, &game, TieBreak::Alphabetical).words,
+            ["jumbo"]
+        );
+    }
+
+    #[test]
+    fn test_compute_letter_frequencies() {
+        let words = ["aaabb", "abbcc", "aaaad"].map(String::from);
+        assert_eq!(
+            compute_letter_frequencies(&words),
+            [('a', 8), ('b', 4), ('c', 2), ('d', 1)]
+        );
+        let profile = profile_from_words(&words, 2, 2);
+        assert_eq!(profile.common, ['a', 'b']);
+        assert_eq!(profile.uncommon, ['d', 'c']);
+        // A letter is never both common and uncommon
+        assert_eq!(profile_from_words(&words, 3, 2).uncommon, ['d']);
+    }
+
+    #[test]
+    fn test_final_guess() {
+        let game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            "-IGHT".chars().collect(),
+            vec![],
+        );
+        let words = ["fight", "night"].map(String::from);
+        let frequencies: HashMap<String, u64> = HashMap::from([("night".to_string(), 900)]);
+        assert_eq!(
+            final_guess(&words, &game, &frequencies).as_deref(),
+            Some("night")
+        );
+        // Both tell the other apart, n is a more common letter than f
+        assert_eq!(
+            final_guess(&words, &game, &HashMap::new()).as_deref(),
+            Some("night")
+        );
+        assert_eq!(final_guess(&[], &game, &HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_rank_by_frequency() {
+        let words = ["xylyl", "slate", "crane", "ourie"].map(String::from);
+        let frequencies: HashMap<String, u64> =
+            HashMap::from([("slate".to_string(), 482_913), ("crane".to_string(), 1200)]);
+        assert_eq!(
+            rank_by_frequency(&words, &frequencies),
+            ["slate", "crane", "xylyl", "ourie"]
+        );
+
+        let game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let blended = blend_frequency_with_common_letters(&words, &game, &frequencies);
+        assert_eq!(blended[0].0, "slate");
+        assert_eq!(blended[3].0, "xylyl");
+        assert!(blended[0].1 > blended[1].1);
+        assert_eq!(common_letter_count("slate", &game), 5);
+    }
+
+    #[test]
+    fn test_hard_mode_candidates() {
+        let mut game = Game::new(
+            crate::GameLanguage::English,
+            crate::GameLength::Five,
+            vec!['-', '-', 'A', '-', 'E'],
+            vec!['r', 'n'],
+        );
+        game.excluded_positions[0].push('s');
+        let probes = ["stale", "slice", "scale", "snake"].map(String::from);
+        assert_eq!(hard_mode_candidates(&probes, &game), probes);
+
+        game.hard_mode = true;
+        // slice misses the green A and snake uses the gray N
+        assert_eq!(hard_mode_candidates(&probes, &game), ["stale", "scale"]);
     }
 }
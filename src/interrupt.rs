@@ -0,0 +1,106 @@
+use std::{
+    fmt::Write,
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use anyhow::Result;
+use nix::{
+    libc,
+    sys::signal::{sigaction, SaFlags, SigAction, SigHandler, SigSet, Signal},
+};
+use wordlehelper::{session::save_session, Game};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+// The handler only sets a flag, the game checks it between prompts. Doing more in the handler
+// could deadlock on a lock held by the interrupted code, like the one on stdout.
+extern "C" fn handle_sigint(_: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::Relaxed);
+}
+
+// Catches Ctrl-C while it's alive. The prompts also see Ctrl-C, they return an error after
+// raising SIGINT.
+pub struct Handler;
+
+impl Handler {
+    pub fn install() -> Result<Self> {
+        set_sigint_handler(SigHandler::Handler(handle_sigint))?;
+        Ok(Self)
+    }
+}
+
+impl Drop for Handler {
+    fn drop(&mut self) {
+        let _ = set_sigint_handler(SigHandler::SigDfl);
+    }
+}
+
+fn set_sigint_handler(handler: SigHandler) -> Result<()> {
+    let action = SigAction::new(handler, SaFlags::empty(), SigSet::empty());
+    // Safe as the handler only touches an atomic
+    unsafe { sigaction(Signal::SIGINT, &action) }?;
+    Ok(())
+}
+
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+}
+
+// The guesses so far, and where the session was saved if there's a path to save it to
+pub fn save_on_interrupt(
+    path: Option<&Path>,
+    game: &Game,
+    possible_words: &[String],
+    guesses: &[String],
+) -> Result<String> {
+    let mut message = format!("Interrupted with {} words left.", possible_words.len());
+    for (index, guess) in guesses.iter().enumerate() {
+        let _ = write!(message, "\n{}. {}", index + 1, guess);
+    }
+    if let Some(path) = path {
+        save_session(path, game, possible_words)?;
+        let _ = write!(
+            message,
+            "\nSaved, continue with --resume {}",
+            path.display()
+        );
+    }
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use wordlehelper::{session::load_session, GameLanguage, GameLength};
+
+    use super::*;
+
+    #[test]
+    fn test_save_on_interrupt() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-', '-', 'a', '-', 'e'],
+            vec!['s', 'l', 't'],
+        );
+        game.update_letter_counts();
+        let words = ["crane", "grape"].map(String::from);
+        let guesses = ["--a-e".to_string()];
+
+        assert_eq!(
+            save_on_interrupt(None, &game, &words, &guesses).unwrap(),
+            "Interrupted with 2 words left.\n1. --a-e"
+        );
+
+        let path = std::env::temp_dir().join("wordlehelper_test_interrupt.session");
+        let message = save_on_interrupt(Some(&path), &game, &words, &guesses).unwrap();
+        assert!(message.ends_with(&format!(
+            "\nSaved, continue with --resume {}",
+            path.display()
+        )));
+        assert_eq!(load_session(&path).unwrap(), (game, words.to_vec()));
+        fs::remove_file(&path).unwrap();
+    }
+}
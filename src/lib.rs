@@ -0,0 +1,1566 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+pub mod feedback;
+pub mod filter;
+pub mod log;
+mod parallel;
+pub mod session;
+pub mod simulate;
+pub mod stats;
+pub mod wordlist;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum GameLength {
+    Four = 4,
+    Five = 5,
+    Six = 6,
+    Seven = 7,
+    Eight = 8,
+}
+
+impl TryFrom<usize> for GameLength {
+    type Error = anyhow::Error;
+
+    fn try_from(length: usize) -> Result<Self, Self::Error> {
+        match length {
+            4 => Ok(Self::Four),
+            5 => Ok(Self::Five),
+            6 => Ok(Self::Six),
+            7 => Ok(Self::Seven),
+            8 => Ok(Self::Eight),
+            _ => anyhow::bail!("Unsupported length {}, expected 4 to 8 letters", length),
+        }
+    }
+}
+
+impl FromStr for GameLength {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let length: usize = value
+            .trim()
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Length {} is not a number", value))?;
+        Self::try_from(length)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GameLanguage {
+    Swedish,
+    English,
+    German,
+    Spanish,
+    French,
+    Italian,
+    Portuguese,
+    Norwegian,
+    Danish,
+}
+
+// The English name of the language or its two letter code, in any case, like "Swedish" or "sv"
+impl FromStr for GameLanguage {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "swedish" | "sv" => Ok(Self::Swedish),
+            "english" | "en" => Ok(Self::English),
+            "german" | "de" => Ok(Self::German),
+            "spanish" | "es" => Ok(Self::Spanish),
+            "french" | "fr" => Ok(Self::French),
+            "italian" | "it" => Ok(Self::Italian),
+            "portuguese" | "pt" => Ok(Self::Portuguese),
+            "norwegian" | "no" | "nb" => Ok(Self::Norwegian),
+            "danish" | "da" => Ok(Self::Danish),
+            _ => anyhow::bail!(
+                "Unknown language {}, expected swedish (sv), english (en), german (de), spanish \
+                 (es), french (fr), italian (it), portuguese (pt), norwegian (no) or danish (da)",
+                value
+            ),
+        }
+    }
+}
+
+// Letters the suggestion filters look for, the most common letters of the language are
+// preferred and words with the uncommon ones are left out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageProfile {
+    pub common: Vec<char>,
+    pub uncommon: Vec<char>,
+}
+
+impl GameLanguage {
+    pub fn profile(self) -> LanguageProfile {
+        let (common, uncommon): (&[char], &[char]) = match self {
+            Self::Swedish => (
+                &['e', 'a', 'n', 'r', 't', 's', 'i', 'l', 'd'],
+                &['q', 'z', 'w', 'x', 'j', 'y'],
+            ),
+            Self::English => (
+                &['e', 't', 'a', 'o', 'i', 'n', 's', 'h', 'l'],
+                &['z', 'q', 'x', 'j', 'v', 'b'],
+            ),
+            Self::German => (
+                &['e', 'n', 'i', 's', 'r', 'a', 't', 'd', 'h'],
+                &['q', 'x', 'y', 'j', 'ß', 'v'],
+            ),
+            Self::Spanish => (
+                &['e', 'a', 'o', 's', 'r', 'n', 'i', 'd', 'l', 'c'],
+                &['w', 'k', 'x', 'z', 'q'],
+            ),
+            Self::French => (
+                &['e', 'a', 's', 'i', 't', 'n', 'r', 'u', 'l', 'o'],
+                &['k', 'w', 'x', 'y', 'z', 'j'],
+            ),
+            Self::Italian => (
+                &['e', 'a', 'i', 'o', 'n', 'r', 't', 'l', 's', 'c'],
+                &['j', 'k', 'w', 'x', 'y'],
+            ),
+            Self::Portuguese => (
+                &['a', 'e', 'o', 's', 'r', 'i', 'd', 'm', 'n', 't'],
+                &['k', 'w', 'y', 'x', 'j'],
+            ),
+            Self::Norwegian => (
+                &['e', 'r', 'n', 't', 's', 'i', 'l', 'a', 'k', 'o'],
+                &['q', 'w', 'x', 'z', 'c'],
+            ),
+            Self::Danish => (
+                &['e', 'r', 'n', 't', 'd', 'a', 'i', 's', 'l', 'g'],
+                &['q', 'w', 'x', 'z', 'c'],
+            ),
+        };
+        LanguageProfile {
+            common: common.to_vec(),
+            uncommon: uncommon.to_vec(),
+        }
+    }
+
+    // Letters used besides a-z, in lower case
+    pub const fn extra_letters(self) -> &'static [char] {
+        match self {
+            Self::Swedish => &['å', 'ä', 'ö'],
+            Self::Norwegian | Self::Danish => &['æ', 'ø', 'å'],
+            Self::English => &[],
+            Self::German => &['ä', 'ö', 'ü', 'ß'],
+            Self::Spanish => &['ñ'],
+            Self::French => &[
+                'à', 'â', 'ç', 'é', 'è', 'ê', 'ë', 'î', 'ï', 'ô', 'ù', 'û', 'ü',
+            ],
+            Self::Italian => &['à', 'è', 'é', 'ì', 'ò', 'ù'],
+            Self::Portuguese => &['á', 'â', 'ã', 'à', 'ç', 'é', 'ê', 'í', 'ó', 'ô', 'õ', 'ú'],
+        }
+    }
+
+    // Vowels of the language, accented ones are vowels of their own like the other letters
+    pub const fn vowels(self) -> &'static [char] {
+        match self {
+            Self::Swedish => &['a', 'e', 'i', 'o', 'u', 'y', 'å', 'ä', 'ö'],
+            Self::Norwegian | Self::Danish => &['a', 'e', 'i', 'o', 'u', 'y', 'æ', 'ø', 'å'],
+            Self::English | Self::Spanish => &['a', 'e', 'i', 'o', 'u'],
+            Self::German => &['a', 'e', 'i', 'o', 'u', 'ä', 'ö', 'ü'],
+            Self::French => &[
+                'a', 'e', 'i', 'o', 'u', 'y', 'à', 'â', 'é', 'è', 'ê', 'ë', 'î', 'ï', 'ô', 'ù',
+                'û', 'ü',
+            ],
+            Self::Italian => &['a', 'e', 'i', 'o', 'u', 'à', 'è', 'é', 'ì', 'ò', 'ù'],
+            Self::Portuguese => &[
+                'a', 'e', 'i', 'o', 'u', 'á', 'â', 'ã', 'à', 'é', 'ê', 'í', 'ó', 'ô', 'õ', 'ú',
+            ],
+        }
+    }
+
+    // a-z followed by the extra letters
+    pub fn alphabet(self) -> Vec<char> {
+        ('a'..='z')
+            .chain(self.extra_letters().iter().copied())
+            .collect()
+    }
+
+    pub fn is_letter(self, letter: char) -> bool {
+        let letter = to_lowercase(letter);
+        letter.is_ascii_lowercase() || self.extra_letters().contains(&letter)
+    }
+}
+
+// What is known about a letter, like the colors of Wordle's keyboard
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LetterState {
+    // Locked in place in some slot
+    Green,
+    // In the word, but not locked in place
+    Yellow,
+    Gray,
+    Unknown,
+}
+
+// Where the guesses ranked by information come from. Only possible words can be the answer, but
+// any allowed guess can be played to learn more about it, like Wordle's larger guess list.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum GuessSource {
+    #[default]
+    PossibleWords,
+    AllowedGuesses,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub language: GameLanguage,
+    pub length: GameLength,
+    pub profile: LanguageProfile,
+    pub playfield: Vec<char>,
+    pub wrong_letters: Vec<char>,
+    // Letters known to be in the word, but not in that slot
+    pub excluded_positions: Vec<Vec<char>>,
+    // Minimum and maximum number of times a letter can occur in the word
+    pub letter_counts: HashMap<char, (usize, usize)>,
+    // Every guess must use the revealed greens and yellows and none of the gray letters
+    pub hard_mode: bool,
+    // Words that shouldn't be suggested, like earlier answers or words already guessed
+    pub banned_words: HashSet<String>,
+    // Letters with accents match the same letter without them, so "etage" matches "étage"
+    pub fold_accents: bool,
+    // solve always narrows the possible words, this is only used when ranking guesses
+    pub guess_source: GuessSource,
+}
+
+impl Game {
+    pub fn new(
+        language: GameLanguage,
+        length: GameLength,
+        playfield: Vec<char>,
+        wrong_letters: Vec<char>,
+    ) -> Self {
+        Self {
+            language,
+            length,
+            profile: language.profile(),
+            // Sized from the playfield, which is what the clues are checked against
+            excluded_positions: vec![vec![]; playfield.len()],
+            playfield,
+            wrong_letters,
+            letter_counts: HashMap::new(),
+            hard_mode: false,
+            banned_words: HashSet::new(),
+            fold_accents: false,
+            guess_source: GuessSource::default(),
+        }
+    }
+
+    // Letter counts known from earlier guesses, narrowed by the current playfield. A letter
+    // entered in the playfield must occur at least as many times as it's entered, and if it's
+    // also entered as a wrong letter it can't occur more times than it's known to occur.
+    pub fn letter_bounds(&self) -> HashMap<char, (usize, usize)> {
+        self.letter_bounds_with(self.entered_letters())
+    }
+
+    // How many times each letter is entered in the playfield
+    fn entered_letters(&self) -> HashMap<char, usize> {
+        let mut entered: HashMap<char, usize> = HashMap::new();
+        for slot in self.playfield.iter().filter(|&&c| c != '-') {
+            for letter in slot.to_lowercase() {
+                *entered.entry(letter).or_default() += 1;
+            }
+        }
+        entered
+    }
+
+    fn letter_bounds_with(&self, entered: HashMap<char, usize>) -> HashMap<char, (usize, usize)> {
+        let mut bounds = self.letter_counts.clone();
+        for (letter, count) in entered {
+            let (min, max) = bounds.entry(letter).or_insert((0, self.length as usize));
+            *min = (*min).max(count);
+            // The cap is the known minimum rather than the count entered now, yellow letters
+            // moved out of the playfield by record_misplaced_letters are still in the minimum
+            if self.wrong_letters.contains(&letter) {
+                *max = (*max).min(*min);
+            }
+        }
+        bounds
+    }
+
+    // Green letters in their slots and yellow letters with the slots they aren't in, entered
+    // apart so the case of the letters doesn't matter. - or space is a slot without a green
+    // letter. The greens replace the playfield while the yellows are added to the ones before.
+    pub fn set_positions(&mut self, green: &str, yellow_slots: &[Vec<char>]) -> anyhow::Result<()> {
+        if let Some(letter) = invalid_playfield_letter(self.language, green) {
+            anyhow::bail!("{} is not a letter", letter);
+        }
+        let playfield: Vec<char> = green
+            .chars()
+            .map(|c| if c == ' ' { '-' } else { to_uppercase(c) })
+            .collect();
+        if playfield.len() != self.length as usize || yellow_slots.len() != playfield.len() {
+            anyhow::bail!(
+                "Green and yellow letters must have {} slots",
+                self.length as usize
+            );
+        }
+        self.playfield = playfield;
+        // Each yellow letter is counted once, like when it's entered in one slot of the playfield
+        let mut entered = self.entered_letters();
+        let mut yellow_letters = HashSet::new();
+        for (excluded, yellow) in self.excluded_positions.iter_mut().zip(yellow_slots) {
+            for letter in yellow.iter().flat_map(|c| c.to_lowercase()) {
+                if !excluded.contains(&letter) {
+                    excluded.push(letter);
+                }
+                if yellow_letters.insert(letter) {
+                    *entered.entry(letter).or_default() += 1;
+                }
+            }
+        }
+        self.letter_counts = self.letter_bounds_with(entered);
+        Ok(())
+    }
+
+    // Add gray letters to the ones entered before, like "rsn". Letters after a -, like "-r", are
+    // removed instead, for letters entered by mistake.
+    pub fn update_wrong_letters(&mut self, letters: &str) {
+        for part in letters.to_lowercase().split_whitespace() {
+            if let Some(removed) = part.strip_prefix('-') {
+                self.wrong_letters
+                    .retain(|letter| !removed.contains(*letter));
+            } else {
+                for letter in part.chars() {
+                    if !self.wrong_letters.contains(&letter) {
+                        self.wrong_letters.push(letter);
+                    }
+                }
+            }
+        }
+    }
+
+    // Letters entered as many times as they at least occur, "rr" for at least two R:s. Entering
+    // the same yellow letter twice in the playfield gives the same minimum. Letters after a =,
+    // like "=e", occur exactly that many times, like when a gray E caps a yellow one.
+    pub fn require_letters(&mut self, letters: &str) {
+        let letters = letters.to_lowercase();
+        let (exact, at_least): (Vec<&str>, Vec<&str>) = letters
+            .split_whitespace()
+            .partition(|part| part.starts_with('='));
+        let length = self.length as usize;
+        for (letter, count) in repeated_letters(&at_least.concat()) {
+            let (min, _) = self.letter_counts.entry(letter).or_insert((0, length));
+            *min = (*min).max(count);
+        }
+        for (letter, count) in repeated_letters(&exact.concat().replace('=', "")) {
+            self.letter_counts.insert(letter, (count, count));
+        }
+    }
+
+    // Forget all clues and bans, settings like the language, length and hard mode are kept
+    pub fn reset(&mut self) {
+        self.playfield = vec!['-'; self.length as usize];
+        self.wrong_letters.clear();
+        self.excluded_positions = vec![vec![]; self.length as usize];
+        self.letter_counts.clear();
+        self.banned_words.clear();
+    }
+
+    pub fn update_letter_counts(&mut self) {
+        self.letter_counts = self.letter_bounds();
+    }
+
+    // Move lower case letters from the playfield to the excluded positions of their slot,
+    // so yellow letters are remembered for later guesses while the playfield keeps the greens.
+    pub fn record_misplaced_letters(&mut self) {
+        for (index, slot) in self.playfield.iter_mut().enumerate() {
+            if slot.is_lowercase() {
+                if !self.excluded_positions[index].contains(slot) {
+                    self.excluded_positions[index].push(*slot);
+                }
+                *slot = '-';
+            }
+        }
+    }
+
+    // Letter is locked in place or known to be somewhere in the word
+    pub fn is_known_letter(&self, letter: char) -> bool {
+        self.playfield
+            .iter()
+            .any(|&c| c.to_lowercase().eq(letter.to_lowercase()))
+            || self
+                .excluded_positions
+                .iter()
+                .flatten()
+                .any(|&c| c == letter)
+    }
+
+    // A letter green in one slot is green even if it's also yellow somewhere else, and a gray
+    // letter that is also green or yellow only caps how many times it occurs
+    pub fn letter_state(&self, letter: char) -> LetterState {
+        let letter = to_lowercase(letter);
+        let (min, max) = self
+            .letter_bounds()
+            .get(&letter)
+            .copied()
+            .unwrap_or((0, self.length as usize));
+        if self
+            .playfield
+            .iter()
+            .any(|&c| c.is_uppercase() && to_lowercase(c) == letter)
+        {
+            LetterState::Green
+        } else if min > 0 || self.is_known_letter(letter) {
+            LetterState::Yellow
+        } else if max == 0 || self.wrong_letters.contains(&letter) {
+            LetterState::Gray
+        } else {
+            LetterState::Unknown
+        }
+    }
+
+    // Letters of the alphabet the clues tell something about, which are the letters of the
+    // guesses so far. In alphabet order.
+    pub fn tried_letters(&self) -> Vec<char> {
+        let unknown_letters = self.unknown_letters();
+        self.language
+            .alphabet()
+            .into_iter()
+            .filter(|letter| !unknown_letters.contains(letter))
+            .collect()
+    }
+
+    // Letters of the alphabet neither known to be in the word nor known not to be, in
+    // alphabet order. Guessing them tells the most about the word.
+    pub fn unknown_letters(&self) -> Vec<char> {
+        let letter_bounds = self.letter_bounds();
+        self.language
+            .alphabet()
+            .into_iter()
+            .filter(|&letter| {
+                let (min, max) = letter_bounds
+                    .get(&letter)
+                    .copied()
+                    .unwrap_or((0, self.length as usize));
+                min == 0
+                    && max > 0
+                    && !self.is_known_letter(letter)
+                    && !self.wrong_letters.contains(&letter)
+            })
+            .collect()
+    }
+
+    // The game with the accents folded out of the clues when accents are folded, so the clues
+    // can be compared with folded words
+    fn folded_clues(&self) -> Cow<'_, Self> {
+        if !self.fold_accents {
+            return Cow::Borrowed(self);
+        }
+        let mut game = self.clone();
+        for slot in &mut game.playfield {
+            *slot = fold_letter(*slot);
+        }
+        game.wrong_letters = Vec::new();
+        for letter in self.wrong_letters.iter().map(|&c| fold_letter(c)) {
+            if !game.wrong_letters.contains(&letter) {
+                game.wrong_letters.push(letter);
+            }
+        }
+        for letters in &mut game.excluded_positions {
+            for letter in letters.iter_mut() {
+                *letter = fold_letter(*letter);
+            }
+        }
+        game.letter_counts = HashMap::new();
+        for (&letter, &(min, max)) in &self.letter_counts {
+            let (folded_min, folded_max) = game
+                .letter_counts
+                .entry(fold_letter(letter))
+                .or_insert((0, 0));
+            *folded_min = (*folded_min).max(min);
+            *folded_max = (*folded_max).max(max);
+        }
+        game.banned_words = self
+            .banned_words
+            .iter()
+            .map(|word| fold_accents(word))
+            .collect();
+        Cow::Owned(game)
+    }
+}
+
+// Works on owned words as well as slices of a WordBuffer
+pub fn solve<W>(game: &Game, possible_words: &[W]) -> Vec<W>
+where
+    W: AsRef<str> + Clone + Send + Sync,
+{
+    let game = &*game.folded_clues();
+    let letter_bounds = game.letter_bounds();
+    // Checking a word is cheap, only split really long lists
+    let words = parallel::filter(possible_words, 4096, |word| {
+        matches_clues(word.as_ref(), game, &letter_bounds)
+    });
+    debug!(
+        "solve removed {} of {} words, {} left",
+        possible_words.len() - words.len(),
+        possible_words.len(),
+        words.len()
+    );
+    if log::enabled(log::Level::Trace) {
+        trace_eliminations(possible_words, game, &letter_bounds);
+    }
+    words
+}
+
+// How many words each kind of clue removed, by the first clue each word doesn't fit
+fn trace_eliminations<W: AsRef<str>>(
+    possible_words: &[W],
+    game: &Game,
+    letter_bounds: &HashMap<char, (usize, usize)>,
+) {
+    let mut removed: Vec<(&str, usize)> = Vec::new();
+    for word in possible_words {
+        if let Err(elimination) = check_clues(word.as_ref(), game, letter_bounds) {
+            match removed
+                .iter_mut()
+                .find(|(clue, _)| *clue == elimination.clue())
+            {
+                Some((_, count)) => *count += 1,
+                None => removed.push((elimination.clue(), 1)),
+            }
+        }
+    }
+    for (clue, count) in removed {
+        trace!("{} words removed by {}", count, clue);
+    }
+}
+
+// Whether a single word fits all the clues of the game, solve checks many words at once
+pub fn word_matches(word: &str, game: &Game) -> bool {
+    check_word(word, game).is_ok()
+}
+
+// The first clue a word doesn't fit, positions are counted from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Elimination {
+    Length(usize),
+    Banned,
+    NotGreen {
+        letter: char,
+        position: usize,
+        green: char,
+    },
+    YellowInSlot {
+        letter: char,
+        position: usize,
+    },
+    YellowMissing(char),
+    Misplaced {
+        letter: char,
+        position: usize,
+    },
+    Absent {
+        letter: char,
+        position: usize,
+    },
+    NotExactly {
+        letter: char,
+        count: usize,
+        exact: usize,
+    },
+    TooFew {
+        letter: char,
+        count: usize,
+        min: usize,
+    },
+    TooMany {
+        letter: char,
+        count: usize,
+        max: usize,
+    },
+    KnownMissing(char),
+}
+
+impl Elimination {
+    // The kind of clue, without the letters and positions
+    const fn clue(self) -> &'static str {
+        match self {
+            Self::Length(_) => "length",
+            Self::Banned => "bans",
+            Self::NotGreen { .. } => "green letters",
+            Self::YellowInSlot { .. } => "yellow letters in their slot",
+            Self::YellowMissing(_) => "missing yellow letters",
+            Self::Misplaced { .. } => "letters known to be misplaced",
+            Self::Absent { .. } => "gray letters",
+            Self::NotExactly { .. } => "exact letter counts",
+            Self::TooFew { .. } => "too few of a letter",
+            Self::TooMany { .. } => "too many of a letter",
+            Self::KnownMissing(_) => "missing known letters",
+        }
+    }
+}
+
+fn matches_clues(
+    possible_word: &str,
+    game: &Game,
+    letter_bounds: &HashMap<char, (usize, usize)>,
+) -> bool {
+    check_clues(possible_word, game, letter_bounds).is_ok()
+}
+
+// Whether a single word fits all the clues, or the first clue it doesn't fit
+pub(crate) fn check_word(word: &str, game: &Game) -> Result<(), Elimination> {
+    let game = game.folded_clues();
+    check_clues(word, &game, &game.letter_bounds())
+}
+
+fn check_clues(
+    possible_word: &str,
+    game: &Game,
+    letter_bounds: &HashMap<char, (usize, usize)>,
+) -> Result<(), Elimination> {
+    // Compare letters by char so multibyte letters like å, ä and ö line up with their slot,
+    // and in lower case so the word list casing doesn't matter.
+    let word = &if game.fold_accents {
+        fold_accents(&possible_word.to_lowercase())
+    } else {
+        possible_word.to_lowercase()
+    };
+    if word.chars().count() != game.playfield.len() {
+        return Err(Elimination::Length(word.chars().count()));
+    }
+    if game.banned_words.contains(word) {
+        return Err(Elimination::Banned);
+    }
+
+    for (index, letter) in word.chars().enumerate() {
+        let slot = game.playfield[index];
+        let position = index + 1;
+        // Ignore words without known correct characters in correct slot
+        if slot.is_uppercase() && letter != to_lowercase(slot) {
+            return Err(Elimination::NotGreen {
+                letter,
+                position,
+                green: to_lowercase(slot),
+            });
+        }
+
+        // Ignore words without a letter in the wrong slot, or with it in that slot
+        if slot.is_lowercase() && letter == slot {
+            return Err(Elimination::YellowInSlot { letter, position });
+        }
+        if slot.is_lowercase() && !word.contains(slot) {
+            return Err(Elimination::YellowMissing(slot));
+        }
+
+        // Ignore words with a letter in a slot where it's known to be misplaced
+        if game.excluded_positions[index].contains(&letter) {
+            return Err(Elimination::Misplaced { letter, position });
+        }
+
+        // Ignore words with letters that is known to not be in the word, unless the letter is
+        // also known to be in the word. Then the letter counts decide how many it may contain.
+        if game.wrong_letters.contains(&letter) && !game.is_known_letter(letter) {
+            return Err(Elimination::Absent { letter, position });
+        }
+    }
+
+    // Ignore words with too few or too many of a letter
+    for (&letter, &(min, max)) in letter_bounds {
+        let count = word.chars().filter(|&c| c == letter).count();
+        if min == max && count != min {
+            return Err(Elimination::NotExactly {
+                letter,
+                count,
+                exact: min,
+            });
+        }
+        if count < min {
+            return Err(Elimination::TooFew { letter, count, min });
+        }
+        if count > max {
+            return Err(Elimination::TooMany { letter, count, max });
+        }
+    }
+
+    // Ignore words missing a letter known to be somewhere in the word
+    if let Some(&letter) = game
+        .excluded_positions
+        .iter()
+        .flatten()
+        .find(|&&c| !word.contains(c))
+    {
+        return Err(Elimination::KnownMissing(letter));
+    }
+
+    Ok(())
+}
+
+// Playfields with one letter changed that some words match, for when a typo in the playfield
+// left no words. The game is the one before the playfield was entered, with the letters not in
+// the word already updated. Returns each playfield with the number of words it matches, the
+// most first.
+pub fn playfield_corrections(
+    game: &Game,
+    playfield: &[char],
+    possible_words: &[String],
+) -> Vec<(String, usize)> {
+    let mut corrections = Vec::new();
+    for index in 0..playfield.len() {
+        for letter in game.language.alphabet() {
+            for replacement in [to_uppercase(letter), letter] {
+                if replacement == playfield[index] {
+                    continue;
+                }
+                let mut corrected = game.clone();
+                corrected.playfield = playfield.to_vec();
+                corrected.playfield[index] = replacement;
+                let entered: String = corrected.playfield.iter().collect();
+                corrected.update_letter_counts();
+                corrected.record_misplaced_letters();
+                let count = solve(&corrected, possible_words).len();
+                if count > 0 {
+                    corrections.push((entered, count));
+                }
+            }
+        }
+    }
+    corrections.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    corrections
+}
+
+// The first char in a playfield that isn't a letter of the language, - or space for an empty slot
+pub fn invalid_playfield_letter(language: GameLanguage, playfield: &str) -> Option<char> {
+    playfield
+        .chars()
+        .find(|&c| c != '-' && c != ' ' && !language.is_letter(c))
+}
+
+// The letter without its accent, like e for é. Letters the languages treat as letters of their
+// own, like ñ and ß, are kept.
+pub const fn fold_letter(letter: char) -> char {
+    match letter {
+        'à' | 'á' | 'â' | 'ã' | 'ä' => 'a',
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' => 'A',
+        'ç' => 'c',
+        'Ç' => 'C',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'É' | 'È' | 'Ê' | 'Ë' => 'E',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'Ì' | 'Í' | 'Î' | 'Ï' => 'I',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' => 'O',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'Ù' | 'Ú' | 'Û' | 'Ü' => 'U',
+        _ => letter,
+    }
+}
+
+pub fn fold_accents(word: &str) -> String {
+    word.chars().map(fold_letter).collect()
+}
+
+// ß has no single char upper case in to_uppercase, use the capital sharp s
+pub(crate) fn to_uppercase(letter: char) -> char {
+    if letter == 'ß' {
+        'ẞ'
+    } else {
+        letter.to_uppercase().next().unwrap_or(letter)
+    }
+}
+
+// Each letter with how many times it's in the letters
+fn repeated_letters(letters: &str) -> HashMap<char, usize> {
+    let mut counts = HashMap::new();
+    for letter in letters.chars() {
+        *counts.entry(letter).or_default() += 1;
+    }
+    counts
+}
+
+pub(crate) fn to_lowercase(letter: char) -> char {
+    letter.to_lowercase().next().unwrap_or(letter)
+}
+
+// Compose letters typed as a base letter followed by a combining diacritic, as some terminals
+// send them, into a single char so the input has one char per slot.
+pub fn compose_letters(input: &str) -> String {
+    let mut composed = String::with_capacity(input.len());
+    for letter in input.chars() {
+        let combined = match (composed.chars().last(), letter) {
+            (Some('a'), '\u{30a}') => Some('å'),
+            (Some('A'), '\u{30a}') => Some('Å'),
+            (Some('a'), '\u{308}') => Some('ä'),
+            (Some('A'), '\u{308}') => Some('Ä'),
+            (Some('o'), '\u{308}') => Some('ö'),
+            (Some('O'), '\u{308}') => Some('Ö'),
+            (Some('u'), '\u{308}') => Some('ü'),
+            (Some('U'), '\u{308}') => Some('Ü'),
+            (Some('n'), '\u{303}') => Some('ñ'),
+            (Some('N'), '\u{303}') => Some('Ñ'),
+            (Some('a'), '\u{300}') => Some('à'),
+            (Some('A'), '\u{300}') => Some('À'),
+            (Some('a'), '\u{302}') => Some('â'),
+            (Some('A'), '\u{302}') => Some('Â'),
+            (Some('c'), '\u{327}') => Some('ç'),
+            (Some('C'), '\u{327}') => Some('Ç'),
+            (Some('e'), '\u{301}') => Some('é'),
+            (Some('E'), '\u{301}') => Some('É'),
+            (Some('e'), '\u{300}') => Some('è'),
+            (Some('E'), '\u{300}') => Some('È'),
+            (Some('e'), '\u{302}') => Some('ê'),
+            (Some('E'), '\u{302}') => Some('Ê'),
+            (Some('e'), '\u{308}') => Some('ë'),
+            (Some('E'), '\u{308}') => Some('Ë'),
+            (Some('i'), '\u{302}') => Some('î'),
+            (Some('I'), '\u{302}') => Some('Î'),
+            (Some('i'), '\u{308}') => Some('ï'),
+            (Some('I'), '\u{308}') => Some('Ï'),
+            (Some('o'), '\u{302}') => Some('ô'),
+            (Some('O'), '\u{302}') => Some('Ô'),
+            (Some('u'), '\u{300}') => Some('ù'),
+            (Some('U'), '\u{300}') => Some('Ù'),
+            (Some('u'), '\u{302}') => Some('û'),
+            (Some('U'), '\u{302}') => Some('Û'),
+            (Some('a'), '\u{301}') => Some('á'),
+            (Some('A'), '\u{301}') => Some('Á'),
+            (Some('a'), '\u{303}') => Some('ã'),
+            (Some('A'), '\u{303}') => Some('Ã'),
+            (Some('i'), '\u{300}') => Some('ì'),
+            (Some('I'), '\u{300}') => Some('Ì'),
+            (Some('i'), '\u{301}') => Some('í'),
+            (Some('I'), '\u{301}') => Some('Í'),
+            (Some('o'), '\u{300}') => Some('ò'),
+            (Some('O'), '\u{300}') => Some('Ò'),
+            (Some('o'), '\u{301}') => Some('ó'),
+            (Some('O'), '\u{301}') => Some('Ó'),
+            (Some('o'), '\u{303}') => Some('õ'),
+            (Some('O'), '\u{303}') => Some('Õ'),
+            (Some('u'), '\u{301}') => Some('ú'),
+            (Some('U'), '\u{301}') => Some('Ú'),
+            _ => None,
+        };
+        if let Some(combined) = combined {
+            composed.pop();
+            composed.push(combined);
+        } else {
+            composed.push(letter);
+        }
+    }
+    composed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['S', '-', 'a', '-', '-'],
+            vec!['t'],
+        );
+        let words = ["salve", "shade", "slate", "sauna"].map(String::from);
+        let returned = solve(&game, &words);
+        assert_eq!(returned, ["salve", "sauna"]);
+    }
+
+    #[test]
+    fn test_word_matches() {
+        // Playfield, wrong letters, word and whether it matches
+        let cases = [
+            ("S-a--", "t", "salve", true),
+            ("S-a--", "t", "sauna", true),
+            ("S-a--", "t", "shade", false),
+            ("S-a--", "t", "slate", false),
+            ("S-a--", "t", "crane", false),
+            ("--R--", "", "carol", true),
+            ("--R--", "", "bread", false),
+            ("-E---", "e", "berth", true),
+            ("-E---", "e", "melee", false),
+            ("-----", "", "crane", true),
+            ("-----", "", "cran", false),
+        ];
+        for (playfield, wrong, word, expected) in cases {
+            let game = Game::new(
+                GameLanguage::English,
+                GameLength::Five,
+                playfield.chars().collect(),
+                wrong.chars().collect(),
+            );
+            assert_eq!(
+                word_matches(word, &game),
+                expected,
+                "{} with playfield {} and wrong letters {:?}",
+                word,
+                playfield,
+                wrong
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_playfield_longer_than_length() {
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            "------".chars().collect(),
+            vec![],
+        );
+        assert_eq!(game.excluded_positions.len(), 6);
+        let words = ["crane", "planet"].map(String::from);
+        assert_eq!(solve(&game, &words), ["planet"]);
+    }
+
+    #[test]
+    fn test_solve_excluded_positions() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-', '-', 'r', '-', '-'],
+            vec![],
+        );
+        game.record_misplaced_letters();
+        assert_eq!(game.playfield, ['-'; 5]);
+        assert_eq!(game.excluded_positions[2], ['r']);
+
+        let words = ["barge", "crane", "mound"].map(String::from);
+        let returned = solve(&game, &words);
+        assert_eq!(returned, ["crane"]);
+    }
+
+    #[test]
+    fn test_solve_duplicate_letter_with_wrong_letter() {
+        // Guessed ERROR, E and the first R in the wrong slot, the other two R:s not in the word
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['e', 'r', '-', '-', '-'],
+            vec!['o', 'r'],
+        );
+        game.update_letter_counts();
+        game.record_misplaced_letters();
+        assert_eq!(game.letter_counts[&'r'], (1, 1));
+        assert_eq!(game.letter_counts[&'e'], (1, 5));
+
+        let words = ["rivet", "refer", "tiger", "ruler"].map(String::from);
+        let returned = solve(&game, &words);
+        assert_eq!(returned, ["rivet", "tiger"]);
+    }
+
+    #[test]
+    fn test_solve_duplicate_letter_locked_and_wrong() {
+        // Guessed ERROR, the middle R in the correct slot, the other two R:s not in the word
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-', '-', 'R', '-', '-'],
+            vec!['e', 'o', 'r'],
+        );
+        let words = ["burnt", "corny", "sorry", "tarry"].map(String::from);
+        let returned = solve(&game, &words);
+        assert_eq!(returned, ["burnt"]);
+    }
+
+    #[test]
+    fn test_solve_german_letters() {
+        // Capital sharp s (ẞ) locks ß in place
+        let game = Game::new(
+            GameLanguage::German,
+            GameLength::Five,
+            vec!['-', '-', 'Ü', 'ẞ', '-'],
+            vec!['i'],
+        );
+        let words = ["grüße", "weiße", "süßen", "blöße"].map(String::from);
+        let returned = solve(&game, &words);
+        assert_eq!(returned, ["grüße"]);
+    }
+
+    #[test]
+    fn test_solve_swedish_letters() {
+        let words = ["bödel", "följa", "ödsla", "kalas", "Östra"].map(String::from);
+
+        let locked = Game::new(
+            GameLanguage::Swedish,
+            GameLength::Five,
+            compose_letters("-O\u{308}---").chars().collect(),
+            vec![],
+        );
+        assert_eq!(locked.playfield, ['-', 'Ö', '-', '-', '-']);
+        assert_eq!(solve(&locked, &words), ["bödel", "följa"]);
+
+        let mut misplaced = Game::new(
+            GameLanguage::Swedish,
+            GameLength::Five,
+            vec!['-', 'ö', '-', '-', '-'],
+            vec!['k'],
+        );
+        misplaced.record_misplaced_letters();
+        assert_eq!(solve(&misplaced, &words), ["ödsla", "Östra"]);
+    }
+
+    #[test]
+    fn test_solve_parallel_matches_sequential() {
+        for (language, length, playfield, wrong_letters) in [
+            (GameLanguage::English, GameLength::Five, "--a-e", "st"),
+            (GameLanguage::Swedish, GameLength::Six, "-A----", "ekl"),
+        ] {
+            let mut game = Game::new(
+                language,
+                length,
+                playfield.chars().collect(),
+                wrong_letters.chars().collect(),
+            );
+            game.update_letter_counts();
+            game.record_misplaced_letters();
+            let words = wordlist::read_file(&game, None).unwrap();
+
+            let letter_bounds = game.letter_bounds();
+            let sequential: Vec<String> = words
+                .iter()
+                .filter(|word| matches_clues(word, &game, &letter_bounds))
+                .cloned()
+                .collect();
+            let parallel = solve(&game, &words);
+            assert!(!parallel.is_empty());
+            assert_eq!(parallel, sequential);
+        }
+    }
+
+    #[test]
+    fn test_solve_logs_word_counts() {
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-', '-', 'A', '-', '-'],
+            vec!['s'],
+        );
+        let words = ["slate", "crane", "shake", "pious"].map(String::from);
+        let (left, records) = log::capture::records(|| solve(&game, &words));
+        assert_eq!(left, ["crane"]);
+        assert!(records.contains(&(
+            log::Level::Debug,
+            "solve removed 3 of 4 words, 1 left".to_string()
+        )));
+        assert!(records.contains(&(
+            log::Level::Trace,
+            "2 words removed by gray letters".to_string()
+        )));
+        assert!(records.contains(&(
+            log::Level::Trace,
+            "1 words removed by green letters".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_solve_four_letters() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::try_from(4).unwrap(),
+            vec!['-', 'O', '-', 'E'],
+            vec!['r', 'c'],
+        );
+        game.update_letter_counts();
+        game.record_misplaced_letters();
+        let words = ["love", "rope", "dome", "echo", "code"].map(String::from);
+        assert_eq!(solve(&game, &words), ["love", "dome"]);
+        assert!(GameLength::try_from(9).is_err());
+    }
+
+    #[test]
+    fn test_invalid_playfield_letter() {
+        assert_eq!(
+            invalid_playfield_letter(GameLanguage::English, "sl4te"),
+            Some('4')
+        );
+        assert_eq!(
+            invalid_playfield_letter(GameLanguage::English, "SL-te"),
+            None
+        );
+        assert_eq!(
+            invalid_playfield_letter(GameLanguage::English, "Ö----"),
+            Some('Ö')
+        );
+        assert_eq!(
+            invalid_playfield_letter(GameLanguage::Swedish, "Ö-å -"),
+            None
+        );
+        assert_eq!(
+            invalid_playfield_letter(GameLanguage::German, "--Üẞ-"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_playfield_corrections() {
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec!['r'],
+        );
+        let words = ["slate", "skate", "state", "crane"].map(String::from);
+        let playfield: Vec<char> = "S-ETE".chars().collect();
+        let mut typo = game.clone();
+        typo.playfield.clone_from(&playfield);
+        assert!(solve(&typo, &words).is_empty());
+        assert_eq!(
+            playfield_corrections(&game, &playfield, &words),
+            [("S-ATE", 3), ("S-kTE", 1), ("S-lTE", 1), ("S-tTE", 1)]
+                .map(|(playfield, count)| (playfield.to_string(), count))
+        );
+    }
+
+    #[test]
+    fn test_set_positions() {
+        let mut entered = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            "-L-t-".chars().collect(),
+            vec!['s', 'a', 'e'],
+        );
+        entered.update_letter_counts();
+        entered.record_misplaced_letters();
+
+        for green in ["-l---", "-L---", " l   "] {
+            let mut positions = Game::new(
+                GameLanguage::English,
+                GameLength::Five,
+                vec!['-'; 5],
+                vec!['s', 'a', 'e'],
+            );
+            let yellow = [vec![], vec![], vec![], vec!['T'], vec![]];
+            positions.set_positions(green, &yellow).unwrap();
+            assert_eq!(positions, entered);
+        }
+
+        let mut game = entered.clone();
+        game.set_positions("-LO--", &vec![vec!['r']; 5]).unwrap();
+        assert_eq!(game.playfield, ['-', 'L', 'O', '-', '-']);
+        assert_eq!(game.excluded_positions[3], ['t', 'r']);
+        assert!(game.set_positions("-l--", &vec![vec![]; 5]).is_err());
+        assert!(game.set_positions("-l---", &vec![vec![]; 4]).is_err());
+        assert!(game.set_positions("-l1--", &vec![vec![]; 5]).is_err());
+    }
+
+    #[test]
+    fn test_update_wrong_letters() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        game.update_wrong_letters("rsn");
+        game.update_wrong_letters("sTx");
+        assert_eq!(game.wrong_letters, ['r', 's', 'n', 't', 'x']);
+        game.update_wrong_letters("");
+        assert_eq!(game.wrong_letters, ['r', 's', 'n', 't', 'x']);
+        game.update_wrong_letters("k -sx");
+        assert_eq!(game.wrong_letters, ['r', 'n', 't', 'k']);
+        game.update_wrong_letters("-q");
+        assert_eq!(game.wrong_letters, ['r', 'n', 't', 'k']);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut game = Game::new(
+            GameLanguage::Swedish,
+            GameLength::Six,
+            "S-a--e".chars().collect(),
+            vec!['t', 'r'],
+        );
+        game.update_letter_counts();
+        game.record_misplaced_letters();
+        game.banned_words.insert("sallad".to_string());
+        game.hard_mode = true;
+        game.reset();
+        assert_eq!(game.playfield, ['-'; 6]);
+        assert!(game.wrong_letters.is_empty());
+        assert_eq!(game.excluded_positions, vec![Vec::<char>::new(); 6]);
+        assert!(game.letter_counts.is_empty());
+        assert!(game.banned_words.is_empty());
+        assert_eq!(game.language, GameLanguage::Swedish);
+        assert_eq!(game.length, GameLength::Six);
+        assert!(game.hard_mode);
+    }
+
+    #[test]
+    fn test_unknown_letters() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['S', '-', 'a', '-', '-'],
+            vec!['l', 't', 'e'],
+        );
+        game.update_letter_counts();
+        game.record_misplaced_letters();
+        let unknown = game.unknown_letters();
+        assert_eq!(unknown.len(), 26 - 5);
+        for letter in ['s', 'a', 'l', 't', 'e'] {
+            assert!(!unknown.contains(&letter));
+        }
+        assert_eq!(&unknown[..3], ['b', 'c', 'd']);
+
+        let game = Game::new(
+            GameLanguage::Swedish,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec!['å'],
+        );
+        let unknown = game.unknown_letters();
+        assert_eq!(unknown.len(), 28);
+        assert_eq!(unknown.last(), Some(&'ö'));
+        assert!(!unknown.contains(&'å'));
+    }
+
+    #[test]
+    fn test_letter_state() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['S', '-', 'a', '-', 'E'],
+            vec!['l', 't', 'e'],
+        );
+        game.update_letter_counts();
+        game.record_misplaced_letters();
+        game.playfield[2] = 'A';
+        game.excluded_positions[0].push('r');
+        assert_eq!(game.letter_state('s'), LetterState::Green);
+        assert_eq!(game.letter_state('A'), LetterState::Green);
+        // Gray too, but that only means there is a single E
+        assert_eq!(game.letter_state('e'), LetterState::Green);
+        assert_eq!(game.letter_state('r'), LetterState::Yellow);
+        assert_eq!(game.letter_state('l'), LetterState::Gray);
+        assert_eq!(game.letter_state('t'), LetterState::Gray);
+        assert_eq!(game.letter_state('b'), LetterState::Unknown);
+
+        game.require_letters("=b");
+        assert_eq!(game.letter_state('b'), LetterState::Yellow);
+        game.letter_counts.insert('c', (0, 0));
+        assert_eq!(game.letter_state('c'), LetterState::Gray);
+    }
+
+    #[test]
+    fn test_solve_two_boards_from_one_guess() {
+        use feedback::Feedback::{Gray, Green, Yellow};
+
+        // Dordle with the secret words crane and pious, both boards get the guess SLATE
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let words = ["crane", "pious", "slate", "snail", "brake", "moist"].map(String::from);
+        let mut first = game.clone();
+        first
+            .apply_feedback("slate", &[Gray, Gray, Green, Gray, Green])
+            .unwrap();
+        let mut second = game;
+        second
+            .apply_feedback("slate", &[Yellow, Gray, Gray, Gray, Gray])
+            .unwrap();
+
+        assert_eq!(solve(&first, &words), ["crane", "brake"]);
+        assert_eq!(solve(&second, &words), ["pious"]);
+    }
+
+    #[test]
+    fn test_solve_banned_words() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['S', '-', '-', '-', '-'],
+            vec![],
+        );
+        game.banned_words.insert("slate".to_string());
+        let words = ["slate", "Shade", "sauna", "crane"].map(String::from);
+        assert_eq!(solve(&game, &words), ["Shade", "sauna"]);
+
+        game.banned_words.insert("shade".to_string());
+        game.playfield[4] = 'A';
+        assert_eq!(solve(&game, &words), ["sauna"]);
+    }
+
+    #[test]
+    fn test_solve_minimum_letter_count() {
+        let words = ["error", "rover", "rebut", "tiger"].map(String::from);
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        game.require_letters("rr");
+        assert_eq!(game.letter_counts[&'r'], (2, 5));
+        assert_eq!(solve(&game, &words), ["error", "rover"]);
+
+        // Rover has two R:s, only error has three
+        game.require_letters("RRR");
+        assert_eq!(solve(&game, &words), ["error"]);
+
+        game.require_letters("=rr t");
+        assert_eq!(game.letter_counts[&'r'], (2, 2));
+        assert_eq!(game.letter_counts[&'t'], (1, 5));
+        assert!(solve(&game, &words).is_empty());
+
+        // The same yellow letter entered twice
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-', 'r', '-', 'r', '-'],
+            vec![],
+        );
+        game.update_letter_counts();
+        game.record_misplaced_letters();
+        assert_eq!(solve(&game, &words), ["rover"]);
+    }
+
+    #[test]
+    fn test_solve_contradicting_clues() {
+        // A can't be both in the wrong slot and locked in that same slot
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-', '-', 'a', '-', '-'],
+            vec![],
+        );
+        game.update_letter_counts();
+        game.record_misplaced_letters();
+        game.playfield[2] = 'A';
+        let words = wordlist::read_file(&game, None).unwrap();
+        assert!(solve(&game, &words).is_empty());
+    }
+
+    #[test]
+    fn test_solve_fold_accents() {
+        let mut game = Game::new(
+            GameLanguage::French,
+            GameLength::Five,
+            "ETAGE".chars().collect(),
+            vec![],
+        );
+        let words = ["étage", "otage", "étape"].map(String::from);
+        assert!(solve(&game, &words).is_empty());
+        game.fold_accents = true;
+        assert_eq!(solve(&game, &words), ["étage"]);
+        assert!(word_matches("étage", &game));
+
+        // Clues typed with accents match words with other accents or none
+        let mut game = Game::new(
+            GameLanguage::French,
+            GameLength::Five,
+            vec!['-', '-', '-', '-', 'É'],
+            vec!['ô'],
+        );
+        game.fold_accents = true;
+        let words = ["crème", "otage", "durée", "arbre"].map(String::from);
+        assert_eq!(solve(&game, &words), ["crème", "durée", "arbre"]);
+        game.banned_words.insert("durée".to_string());
+        assert_eq!(solve(&game, &words), ["crème", "arbre"]);
+
+        assert_eq!(fold_accents("Ça, où être"), "Ca, ou etre");
+        assert_eq!(fold_accents("niño straße"), "niño straße");
+        assert_eq!(compose_letters("e\u{301}tage"), "étage");
+    }
+
+    #[test]
+    fn test_solve_spanish_letters() {
+        let game = Game::new(
+            GameLanguage::Spanish,
+            GameLength::Five,
+            compose_letters("--N\u{303}--").chars().collect(),
+            vec!['n'],
+        );
+        assert_eq!(game.playfield, ['-', '-', 'Ñ', '-', '-']);
+        let words = wordlist::read_file(&game, None).unwrap();
+        let returned = solve(&game, &words);
+        assert!(returned.contains(&"señor".to_string()));
+        assert!(returned.contains(&"señal".to_string()));
+        assert!(!returned.contains(&"niños".to_string()));
+        // Ñ is its own letter, an N in the word doesn't match it
+        assert!(returned.iter().all(|word| word.chars().nth(2) == Some('ñ')));
+        assert!(returned.iter().all(|word| !word.contains('n')));
+    }
+
+    #[test]
+    fn test_solve_italian_and_portuguese() {
+        let game = Game::new(
+            GameLanguage::Italian,
+            GameLength::Five,
+            "-ITT-".chars().collect(),
+            vec!['o'],
+        );
+        let words = wordlist::read_file(&game, None).unwrap();
+        assert_eq!(solve(&game, &words), ["città"]);
+
+        let mut game = Game::new(
+            GameLanguage::Portuguese,
+            GameLength::Five,
+            compose_letters("I--A\u{303}O").chars().collect(),
+            vec![],
+        );
+        assert_eq!(game.playfield, ['I', '-', '-', 'Ã', 'O']);
+        let words = wordlist::read_file(&game, None).unwrap();
+        assert_eq!(solve(&game, &words), ["irmão"]);
+        // Ã is its own letter unless accents are folded
+        game.playfield = "I--AO".chars().collect();
+        assert!(solve(&game, &words).is_empty());
+        game.fold_accents = true;
+        assert_eq!(solve(&game, &words), ["irmão"]);
+    }
+
+    #[test]
+    fn test_solve_norwegian_and_danish() {
+        for language in [GameLanguage::Norwegian, GameLanguage::Danish] {
+            let game = Game::new(
+                language,
+                GameLength::Five,
+                "-ØLSE".chars().collect(),
+                vec![],
+            );
+            let words = wordlist::read_file(&game, None).unwrap();
+            assert_eq!(solve(&game, &words), ["pølse"], "{:?}", language);
+        }
+        // Ø is a letter of its own, not an O with a stroke
+        let mut game = Game::new(
+            GameLanguage::Norwegian,
+            GameLength::Five,
+            "BJ-R-".chars().collect(),
+            vec!['ø'],
+        );
+        let words = wordlist::read_file(&game, None).unwrap();
+        assert!(solve(&game, &words).is_empty());
+        game.fold_accents = true;
+        assert!(solve(&game, &words).is_empty());
+        assert!(!GameLanguage::Swedish.is_letter('ø'));
+        assert!(GameLanguage::Danish.is_letter('Æ'));
+    }
+
+    #[test]
+    fn test_solve_exact_letter_count() {
+        let words = ["slate", "eerie", "elder", "tiger"].map(String::from);
+        let mut at_least = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        at_least.require_letters("e");
+        assert_eq!(
+            solve(&at_least, &words),
+            ["slate", "eerie", "elder", "tiger"]
+        );
+
+        // Guessed GEESE against TIGER, the first E is yellow and the other two gray
+        let mut exactly = at_least.clone();
+        let feedback = feedback::compute_feedback("geese", "tiger");
+        exactly.apply_feedback("geese", &feedback).unwrap();
+        assert_eq!(exactly.letter_counts[&'e'], (1, 1));
+        assert_eq!(solve(&exactly, &words), ["tiger"]);
+
+        let mut marked = at_least.clone();
+        marked.require_letters("=e");
+        assert_eq!(solve(&marked, &words), ["slate", "tiger"]);
+    }
+
+    #[test]
+    fn test_parse_language_and_length() {
+        for (value, language) in [
+            ("swedish", GameLanguage::Swedish),
+            ("SV", GameLanguage::Swedish),
+            ("English", GameLanguage::English),
+            ("en", GameLanguage::English),
+            ("de", GameLanguage::German),
+            ("Spanish", GameLanguage::Spanish),
+            ("fr", GameLanguage::French),
+            ("it", GameLanguage::Italian),
+            ("portuguese", GameLanguage::Portuguese),
+            ("Norwegian", GameLanguage::Norwegian),
+            ("nb", GameLanguage::Norwegian),
+            ("da", GameLanguage::Danish),
+        ] {
+            assert_eq!(
+                value.parse::<GameLanguage>().unwrap(),
+                language,
+                "{}",
+                value
+            );
+        }
+        for invalid in ["klingon", "", "eng", "s"] {
+            assert!(invalid.parse::<GameLanguage>().is_err(), "{}", invalid);
+        }
+        let error = "klingon".parse::<GameLanguage>().unwrap_err();
+        assert!(error.to_string().contains("english (en)"));
+
+        assert_eq!("5".parse::<GameLength>().unwrap(), GameLength::Five);
+        assert_eq!(" 6 ".parse::<GameLength>().unwrap(), GameLength::Six);
+        assert!("3".parse::<GameLength>().is_err());
+        assert!("five".parse::<GameLength>().is_err());
+        assert!("-5".parse::<GameLength>().is_err());
+    }
+
+    #[test]
+    fn test_solve_clue_matrix() {
+        let words: Vec<String> = [
+            "error", "rover", "robot", "order", "eerie", "geese", "sheep", "speed", "crane",
+            "react", "trace", "cater", "abbey", "babes", "llama", "allay", "hello", "lemon",
+            "melon", "sassy",
+        ]
+        .map(String::from)
+        .to_vec();
+        for guess in &words {
+            for answer in &words {
+                let feedback = feedback::compute_feedback(guess, answer);
+                let same_feedback: Vec<String> = words
+                    .iter()
+                    .filter(|word| feedback::compute_feedback(guess, word) == feedback)
+                    .cloned()
+                    .collect();
+
+                let mut game = Game::new(
+                    GameLanguage::English,
+                    GameLength::Five,
+                    vec!['-'; 5],
+                    vec![],
+                );
+                game.apply_feedback(guess, &feedback).unwrap();
+                assert_eq!(solve(&game, &words), same_feedback, "{} {}", guess, answer);
+
+                // As typed in the playfield, which can't tell which slot a gray duplicate was in,
+                // so it may keep more words but never drop one
+                let playfield = guess
+                    .chars()
+                    .zip(&feedback)
+                    .map(|(letter, result)| match result {
+                        feedback::Feedback::Green => to_uppercase(letter),
+                        feedback::Feedback::Yellow => letter,
+                        feedback::Feedback::Gray => '-',
+                    })
+                    .collect();
+                let wrong_letters = guess
+                    .chars()
+                    .zip(&feedback)
+                    .filter(|&(_, &result)| result == feedback::Feedback::Gray)
+                    .map(|(letter, _)| letter)
+                    .collect();
+                let mut typed = Game::new(
+                    GameLanguage::English,
+                    GameLength::Five,
+                    playfield,
+                    wrong_letters,
+                );
+                typed.update_letter_counts();
+                typed.record_misplaced_letters();
+                let kept = solve(&typed, &words);
+                for word in &same_feedback {
+                    assert!(kept.contains(word), "{} {} dropped {}", guess, answer, word);
+                }
+                let has_duplicates = guess.chars().any(|c| guess.matches(c).count() > 1);
+                if !has_duplicates {
+                    assert_eq!(kept, same_feedback, "{} {}", guess, answer);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    env, fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// Leveled logging to stderr, silent unless turned on with RUST_LOG like RUST_LOG=debug or
+// RUST_LOG=wordlehelper=trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        }
+    }
+}
+
+// 0 is off
+static MAX_LEVEL: AtomicUsize = AtomicUsize::new(0);
+
+pub fn init_from_env() {
+    if let Ok(filter) = env::var("RUST_LOG") {
+        set_max_level(parse_filter(&filter));
+    }
+}
+
+pub fn set_max_level(level: Option<Level>) {
+    MAX_LEVEL.store(level.map_or(0, |level| level as usize), Ordering::Relaxed);
+}
+
+// Comma separated levels, for all targets or for targets starting with wordlehelper. Other
+// targets, like other crates, have nothing to log here.
+fn parse_filter(filter: &str) -> Option<Level> {
+    filter
+        .split(',')
+        .filter_map(|directive| {
+            let directive = directive.trim();
+            let (target, level) = directive.split_once('=').unwrap_or(("", directive));
+            if !target.is_empty() && !target.starts_with("wordlehelper") {
+                return None;
+            }
+            match level.to_lowercase().as_str() {
+                "error" => Some(Level::Error),
+                "warn" => Some(Level::Warn),
+                "info" => Some(Level::Info),
+                "debug" => Some(Level::Debug),
+                "trace" => Some(Level::Trace),
+                // A target without a level logs everything
+                _ if target.is_empty() && level.starts_with("wordlehelper") => Some(Level::Trace),
+                _ => None,
+            }
+        })
+        .max()
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as usize <= MAX_LEVEL.load(Ordering::Relaxed) || capture::active()
+}
+
+pub fn log(level: Level, target: &str, args: fmt::Arguments) {
+    if capture::active() {
+        capture::record(level, args.to_string());
+    } else {
+        eprintln!("[{} {}] {}", level.name(), target, args);
+    }
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            $crate::log::log($crate::log::Level::Debug, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            $crate::log::log($crate::log::Level::Trace, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+// Tests collect the records logged on their own thread, so tests running at the same time don't
+// see each other's records
+#[cfg(test)]
+pub mod capture {
+    use std::cell::RefCell;
+
+    use super::Level;
+
+    thread_local! {
+        static RECORDS: RefCell<Option<Vec<(Level, String)>>> = const { RefCell::new(None) };
+    }
+
+    pub fn active() -> bool {
+        RECORDS.with(|records| records.borrow().is_some())
+    }
+
+    pub fn record(level: Level, message: String) {
+        RECORDS.with(|records| {
+            if let Some(records) = records.borrow_mut().as_mut() {
+                records.push((level, message));
+            }
+        });
+    }
+
+    // Everything logged on this thread while f runs, at every level
+    pub fn records<T>(f: impl FnOnce() -> T) -> (T, Vec<(Level, String)>) {
+        RECORDS.with(|records| *records.borrow_mut() = Some(Vec::new()));
+        let result = f();
+        let records = RECORDS.with(|records| records.borrow_mut().take());
+        (result, records.unwrap_or_default())
+    }
+}
+
+#[cfg(not(test))]
+mod capture {
+    use super::Level;
+
+    pub const fn active() -> bool {
+        false
+    }
+
+    pub fn record(_level: Level, _message: String) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(parse_filter("debug"), Some(Level::Debug));
+        assert_eq!(parse_filter("wordlehelper=trace"), Some(Level::Trace));
+        assert_eq!(parse_filter("wordlehelper::filter=info"), Some(Level::Info));
+        assert_eq!(parse_filter("warn, wordlehelper=debug"), Some(Level::Debug));
+        assert_eq!(parse_filter("wordlehelper"), Some(Level::Trace));
+        assert_eq!(parse_filter("dialoguer=trace"), None);
+        assert_eq!(parse_filter("off"), None);
+        assert_eq!(parse_filter(""), None);
+    }
+
+    #[test]
+    fn test_capture() {
+        let ((), records) = capture::records(|| {
+            crate::debug!("{} words", 3);
+            crate::trace!("details");
+        });
+        assert_eq!(
+            records,
+            [
+                (Level::Debug, "3 words".to_string()),
+                (Level::Trace, "details".to_string())
+            ]
+        );
+        assert!(!capture::active());
+    }
+}
@@ -1,54 +1,162 @@
-use std::{fs, io, io::prelude::*};
+use std::{cmp::Reverse, collections::HashMap, path::Path};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use wordlehelper::{
+    compose_letters,
+    feedback::{
+        apply_batch, compute_feedback, parse_code_feedback, parse_emoji_feedback, Feedback,
+    },
+    filter, fold_accents, invalid_playfield_letter, playfield_corrections,
+    session::{load_session, save_session},
+    simulate::{benchmark_with_progress, puzzle_answer, simulate},
+    solve,
+    stats::{record_game_result, GameResult},
+    wordlist::{
+        infer_length, read_allowed_guesses, read_file_with, read_frequencies, write_words,
+        InvalidWords, SkippedWords, WordListCache,
+    },
+    Game, GameLanguage, GameLength,
+};
 
-pub mod filter;
+use crate::{
+    cli::{OutputFormat, Strategy},
+    output::{print_scored_words, print_words, WordOrder},
+    repl::Command,
+};
+
+mod boards;
+mod cli;
+mod config;
+mod interrupt;
+mod output;
+mod repl;
+mod sparkline;
+
+// Few enough words left to pick the next guess from a list instead of typing it
+const PICK_LIMIT: usize = 8;
+
+// How many times the playfield and wrong letters are asked for when reading them fails
+const INPUT_ATTEMPTS: usize = 3;
 
 #[derive(Debug, Copy, Clone)]
-#[repr(u8)]
-enum GameLength {
-    Five = 5,
-    Six = 6,
+enum MenuItem {
+    UpdatePlayfield,
+    PickWord,
+    ShowAllWords,
+    PasteFeedback,
+    AnalyzeGuess,
+    ExplainWord,
+    LettersBySlot,
+    PatternSearch,
+    BanWord,
+    RepeatedLetters,
+    ExportWords,
+    WordOrder,
+    InputStyle,
+    Restart,
+    Undo,
+    Help,
+    SaveAndQuit,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub enum GameLanguage {
-    Swedish,
-    English,
+impl MenuItem {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::UpdatePlayfield => "Update playfield",
+            Self::PickWord => "Pick the word to guess",
+            Self::ShowAllWords => "Show all possible words",
+            Self::PasteFeedback => "Paste feedback row",
+            Self::AnalyzeGuess => "Analyze a guess",
+            Self::ExplainWord => "Why isn't a word shown?",
+            Self::LettersBySlot => "Show letters by slot",
+            Self::PatternSearch => "Search word list by pattern",
+            Self::BanWord => "Ban a word",
+            Self::RepeatedLetters => "Enter repeated letters",
+            Self::ExportWords => "Export possible words to a file",
+            Self::WordOrder => "Change word order",
+            Self::InputStyle => "Change input style",
+            Self::Restart => "Restart current game",
+            Self::Undo => "Undo last guess",
+            Self::Help => "How to enter clues",
+            Self::SaveAndQuit => "Save and quit",
+        }
+    }
 }
 
-#[derive(Debug)]
-pub struct Game {
-    language: GameLanguage,
-    length: GameLength,
-    playfield: Vec<char>,
-    wrong_letters: Vec<char>,
+// How the clues of a guess are entered
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+enum InputStyle {
+    // Green letters in CAPITALS and yellow in lowercase, then the gray letters
+    #[default]
+    Playfield,
+    // The guess and a code like BGBYB
+    WordleCodes,
+    // Green letters in their slots, then yellow letters with their slots, in any case
+    Positions,
 }
 
-impl Game {
-    fn new_game() -> Self {
-        let language = {
-            let input = Select::with_theme(&ColorfulTheme::default())
-                .with_prompt("Game language?")
-                .default(0)
-                .item("Swedish")
-                .item("English")
-                .interact_opt()
-                .expect("English, Swedish or exit should be only choices.");
+impl InputStyle {
+    const fn next(self) -> Self {
+        match self {
+            Self::Playfield => Self::WordleCodes,
+            Self::WordleCodes => Self::Positions,
+            Self::Positions => Self::Playfield,
+        }
+    }
 
-            match input {
-                Some(0) => GameLanguage::Swedish,
-                Some(1) => GameLanguage::English,
-                _ => std::process::exit(0),
-            }
-        };
-        let length = if language == GameLanguage::English {
-            GameLength::Five
-        } else {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Playfield => "playfield",
+            Self::WordleCodes => "Wordle codes",
+            Self::Positions => "green and yellow positions",
+        }
+    }
+}
+
+// The length is only asked for when it isn't given by a custom word list. The defaults from the
+// config file are selected first, or used without asking with --use-defaults.
+fn new_game(length: Option<GameLength>, args: &cli::Args) -> Game {
+    let languages = [
+        GameLanguage::Swedish,
+        GameLanguage::English,
+        GameLanguage::German,
+        GameLanguage::Spanish,
+        GameLanguage::French,
+        GameLanguage::Italian,
+        GameLanguage::Portuguese,
+        GameLanguage::Norwegian,
+        GameLanguage::Danish,
+    ];
+    let default_language = args.default_language.filter(|_| args.use_defaults);
+    let language = default_language.unwrap_or_else(|| {
+        let default = args
+            .default_language
+            .and_then(|language| languages.iter().position(|&l| l == language))
+            .unwrap_or(0);
+        let input = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Game language?")
+            .default(default)
+            .item("Swedish")
+            .item("English")
+            .item("German")
+            .item("Spanish")
+            .item("French")
+            .item("Italian")
+            .item("Portuguese")
+            .item("Norwegian")
+            .item("Danish")
+            .interact_opt()
+            .expect("A language in the list or exit should be only choices.");
+
+        input.map_or_else(|| std::process::exit(0), |index| languages[index])
+    });
+    let default_length = args.default_length.filter(|_| args.use_defaults);
+    let length = length.or(default_length).unwrap_or_else(|| {
+        if language == GameLanguage::Swedish {
             let input = Select::with_theme(&ColorfulTheme::default())
                 .with_prompt("Playfield size?")
-                .default(0)
+                .default(usize::from(args.default_length == Some(GameLength::Six)))
                 .item("Five letters")
                 .item("Six letters")
                 .interact()
@@ -59,200 +167,1408 @@ impl Game {
                 1 => GameLength::Six,
                 _ => unreachable!(),
             }
-        };
-        Self {
-            language,
-            length,
-            playfield: vec!['-'; length as usize],
-            wrong_letters: vec![],
+        } else {
+            GameLength::Five
         }
-    }
+    });
+    Game::new(language, length, vec!['-'; length as usize], vec![])
 }
 
 fn main() -> Result<()> {
+    wordlehelper::log::init_from_env();
+    let mut args = cli::Args::parse()?;
+    if args.help {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+    if args.version {
+        print!("{}", output::version_info());
+        return Ok(());
+    }
+    args.apply_config(config::load_config()?);
+    // Colors are already off when stdout or stderr isn't a terminal
+    let no_color_env = std::env::var_os("NO_COLOR");
+    console::set_colors_enabled(output::colors_enabled(
+        args.no_color,
+        no_color_env.as_deref(),
+        console::colors_enabled(),
+    ));
+    console::set_colors_enabled_stderr(output::colors_enabled(
+        args.no_color,
+        no_color_env.as_deref(),
+        console::colors_enabled_stderr(),
+    ));
+    if args.no_clear {
+        output::set_clear_screen_enabled(false);
+    }
+    if let Some(max_print) = args.max_print {
+        output::set_max_print(max_print);
+    }
+    if let Some(secret) = &args.simulate {
+        return play_simulation(&args, secret);
+    }
+    if let Some(puzzle) = args.puzzle {
+        return play_simulation(&args, &puzzle_secret(&args, puzzle)?);
+    }
+    if args.batch {
+        return play_batch(&args);
+    }
+    if args.benchmark {
+        return play_benchmark(&args);
+    }
+    if args.is_non_interactive() {
+        return play_non_interactive(&args);
+    }
+    let mut cache = RunCache {
+        word_lists: WordListCache::new(args.invalid_words(), args.extra_wordlist.clone()),
+        ..RunCache::default()
+    };
+    // Only the first game is resumed, the following ones are new games
+    let mut resume = args.resume.clone();
     loop {
         println!("Welcome to Wordlehelper! Press q or <Esc> to quit.");
-        play_game()?;
+        if args.boards > 1 {
+            let length = args.wordlist.as_deref().map(infer_length).transpose()?;
+            let mut game = new_game(length, &args);
+            game.fold_accents = args.fold_accents;
+            let words = read_words(&mut game, args.wordlist.as_deref(), &mut cache.word_lists)?;
+            boards::play_boards(&game, &words, args.boards)?;
+        } else {
+            let resume = resume.take();
+            if args.repl {
+                play_repl(&args, resume.as_deref(), &mut cache)?;
+            } else {
+                play_game(&args, resume.as_deref(), &mut cache)?;
+            }
+        }
+    }
+}
+
+// What's kept between the games of one run, so it's only read or computed once
+#[derive(Default)]
+struct RunCache {
+    word_lists: WordListCache,
+    // Best opening word for each word list
+    opening_words: HashMap<(GameLanguage, GameLength), Option<String>>,
+    // Best pair of opening words for each word list, searching the pairs takes a while
+    opening_pairs: HashMap<(GameLanguage, GameLength), Option<(String, String)>>,
+}
+
+// The common and uncommon letters of a custom word list are taken from the list itself, as it
+// can be in any language
+fn read_words(
+    game: &mut Game,
+    wordlist: Option<&Path>,
+    word_lists: &mut WordListCache,
+) -> Result<Vec<String>> {
+    let words = read_word_slices(game, wordlist, word_lists)?;
+    Ok(words.into_iter().map(String::from).collect())
+}
+
+// The words of the cached list without copying them, for runs that only solve once
+fn read_word_slices<'a>(
+    game: &mut Game,
+    wordlist: Option<&Path>,
+    word_lists: &'a mut WordListCache,
+) -> Result<Vec<&'a str>> {
+    let (words, skipped) = word_lists.read_buffer(game, wordlist)?;
+    for skipped in &skipped {
+        warn_skipped(skipped);
+    }
+    let words = words.words();
+    if wordlist.is_some() {
+        game.profile = filter::profile_from_words(&words, 9, 6);
+    }
+    Ok(words)
+}
+
+// The library only returns the skipped words, the warnings go to stderr like other messages
+fn warn_skipped(skipped: &SkippedWords) {
+    for warning in skipped.warnings() {
+        eprintln!("{}", warning);
+    }
+}
+
+// The bundled lists are in alphabetical order, so the answers must come from --wordlist. Words
+// that aren't letters are rejected rather than skipped, skipping one would shift the numbers.
+fn puzzle_secret(args: &cli::Args, puzzle: usize) -> Result<String> {
+    let Some(path) = args.wordlist.as_deref() else {
+        bail!(
+            "--puzzle needs --wordlist with the answers, one per line in the order they were used"
+        );
+    };
+    let length = infer_length(path)?;
+    let game = Game::new(
+        args.language_or_default(),
+        length,
+        vec!['-'; length as usize],
+        vec![],
+    );
+    let (answers, skipped) = read_file_with(&game, Some(path), InvalidWords::Reject)?;
+    warn_skipped(&skipped);
+    let answer = puzzle_answer(&answers, puzzle)?;
+    println!("Puzzle {} is {}.", puzzle, answer.to_uppercase());
+    Ok(answer.to_string())
+}
+
+fn play_simulation(args: &cli::Args, secret: &str) -> Result<()> {
+    let language = args.language_or_default();
+    let length = GameLength::try_from(compose_letters(secret).chars().count())?;
+    let game = Game::new(language, length, vec!['-'; length as usize], vec![]);
+    let (words, skipped) = read_file_with(&game, args.wordlist.as_deref(), args.invalid_words())?;
+    warn_skipped(&skipped);
+    let secret = compose_letters(secret).to_lowercase();
+    let guesses = simulate(&secret, &game, &words)?;
+    for guess in &guesses {
+        println!("{}", guess.to_uppercase());
+    }
+    let solved = guesses.last().is_some_and(|guess| *guess == secret);
+    if solved {
+        println!("Solved in {} guesses.", guesses.len());
+    } else {
+        println!("{} is not in the word list.", secret.to_uppercase());
+    }
+    if let Some(path) = &args.stats_file {
+        let result = GameResult {
+            language,
+            length,
+            guesses: guesses.len(),
+            solved,
+        };
+        record_game_result(path, &result)?;
+    }
+    Ok(())
+}
+
+// Every game starts without clues, only the language and length of the options are used
+fn play_benchmark(args: &cli::Args) -> Result<()> {
+    let options = game_from_args(args)?;
+    let length = options.length;
+    let game = Game::new(options.language, length, vec!['-'; length as usize], vec![]);
+    let (words, skipped) = read_file_with(&game, args.wordlist.as_deref(), args.invalid_words())?;
+    warn_skipped(&skipped);
+    let progress = output::ProgressBar::new("Playing", words.len());
+    let report = benchmark_with_progress(&game, &words, |done, total| {
+        progress.update(done, total);
+    })?;
+    drop(progress);
+    println!("{}", output::benchmark_histogram(&report));
+    Ok(())
+}
+
+// The game given by the options, with an empty playfield when none is given
+fn game_from_args(args: &cli::Args) -> Result<Game> {
+    let language = args.language_or_default();
+    let length = match (args.length.or(args.default_length), &args.wordlist) {
+        (Some(length), _) => length,
+        (None, Some(path)) => infer_length(path)?,
+        (None, None) => GameLength::Five,
+    };
+    let playfield: Vec<char> = args.playfield.as_ref().map_or_else(
+        || vec!['-'; length as usize],
+        |playfield| {
+            compose_letters(playfield)
+                .replace(' ', "-")
+                .chars()
+                .collect()
+        },
+    );
+    if let Some(letter) = invalid_playfield_letter(language, &playfield.iter().collect::<String>())
+    {
+        bail!("{} is not a letter", letter);
+    }
+    if playfield.len() != length as usize {
+        bail!("Too few/many letters in playfield");
+    }
+    let wrong_letters = args
+        .wrong
+        .as_deref()
+        .map(|wrong| compose_letters(wrong.trim()).to_lowercase())
+        .unwrap_or_default()
+        .chars()
+        .collect();
+
+    let mut game = Game::new(language, length, playfield, wrong_letters);
+    if let Some(present) = &args.present {
+        game.excluded_positions = cli::parse_present(&compose_letters(present), language, length)?;
+    }
+    game.fold_accents = args.fold_accents;
+    game.update_letter_counts();
+    game.record_misplaced_letters();
+    if let Some((guess, feedback)) = args.first_guess()? {
+        game.apply_feedback(&guess, &feedback)?;
+    }
+    Ok(game)
+}
+
+fn play_non_interactive(args: &cli::Args) -> Result<()> {
+    let mut game = game_from_args(args)?;
+    let mut word_lists = WordListCache::new(args.invalid_words(), args.extra_wordlist.clone());
+    let words = read_word_slices(&mut game, args.wordlist.as_deref(), &mut word_lists)?;
+    let possible_words: Vec<String> = solve(&game, &words).into_iter().map(String::from).collect();
+    print_possible_words(args, &game, &possible_words);
+    export_to_file(args, &possible_words)
+}
+
+// Clues are read from stdin, one guess and its pattern per line
+fn play_batch(args: &cli::Args) -> Result<()> {
+    let mut game = game_from_args(args)?;
+    let mut word_lists = WordListCache::new(args.invalid_words(), args.extra_wordlist.clone());
+    let words = read_word_slices(&mut game, args.wordlist.as_deref(), &mut word_lists)?;
+    let lines = std::io::stdin()
+        .lines()
+        .collect::<Result<Vec<String>, _>>()?;
+    let possible_words: Vec<String> = apply_batch(&mut game, &words, &lines)?
+        .into_iter()
+        .map(String::from)
+        .collect();
+    print_possible_words(args, &game, &possible_words);
+    export_to_file(args, &possible_words)
+}
+
+// With --export the words are written to the file, the message goes to stderr so it isn't mixed
+// with the printed words
+fn export_to_file(args: &cli::Args, possible_words: &[String]) -> Result<()> {
+    if let Some(path) = &args.export {
+        write_words(path, possible_words)?;
+        eprintln!("{}", output::exported_message(possible_words.len(), path));
     }
+    Ok(())
 }
 
-fn play_game() -> Result<()> {
-    let mut current_game = Game::new_game();
-    let mut possible_words = read_file(&current_game)?;
+fn print_possible_words(args: &cli::Args, game: &Game, possible_words: &[String]) {
+    match args.format {
+        OutputFormat::Text => {
+            for word in possible_words {
+                println!("{}", word);
+            }
+        }
+        OutputFormat::Json => {
+            let suggestions =
+                filter::suggest(game, possible_words, args.tie_break(), args.keep_duplicates);
+            // The words without duplicates are listed even when the filter is skipped
+            let no_duplicates = suggestions
+                .no_duplicates
+                .unwrap_or_else(|| filter::words_without_duplicate_letters(possible_words));
+            println!(
+                "{}",
+                output::words_to_json(&[
+                    ("possible_words", possible_words),
+                    ("without_duplicate_letters", &no_duplicates.words),
+                    ("best_guesses", &suggestions.best_guesses.words),
+                ])
+            );
+        }
+    }
+}
 
+// A resumed or new game with its word list, printing the opening word and instructions
+fn print_openers(game: &Game, words: &[String], cache: &mut RunCache) {
+    let opening_word = cache
+        .opening_words
+        .entry((game.language, game.length))
+        .or_insert_with(|| filter::best_opening_word(words, game));
+    if let Some(word) = opening_word {
+        println!("Try starting with {}.", word.to_uppercase());
+    }
+    let opening_pair = cache
+        .opening_pairs
+        .entry((game.language, game.length))
+        .or_insert_with(|| filter::best_opening_pair(words, game));
+    if let Some((first, second)) = opening_pair {
+        println!(
+            "Best two-word opener: {} + {}.",
+            first.to_uppercase(),
+            second.to_uppercase()
+        );
+    }
+}
+
+fn start_game(
+    resume: Option<&Path>,
+    args: &cli::Args,
+    cache: &mut RunCache,
+) -> Result<(Game, Vec<String>)> {
+    let wordlist = args.wordlist.as_deref();
+    // A resumed game is past its opening and its words are what's left of the list, so
+    // the openers are only computed for new games
+    let (current_game, possible_words) = if let Some(path) = resume {
+        load_session(path)?
+    } else {
+        let length = wordlist.map(infer_length).transpose()?;
+        let mut game = new_game(length, args);
+        game.fold_accents = args.fold_accents;
+        let words = read_words(&mut game, wordlist, &mut cache.word_lists)?;
+        print_openers(&game, &words, cache);
+        (game, words)
+    };
+    println!();
+
+    if args.quiet {
+        return Ok((current_game, possible_words));
+    }
     println!(
-        "Use CAPITAL letters for letters in correct slot.\n\
-        Use lower case letters for letters in the wrong slot.\n\
+        "Use CAPITAL letters for letters in correct slot (green).\n\
+        Use lower case letters for letters in the wrong slot (yellow), \
+        they are remembered for that slot in later guesses.\n\
         Leave the - or use space if the slot is empty.\n"
     );
+    if current_game.language == GameLanguage::German {
+        println!("Use ẞ (capital ß) for ß in correct slot.\n");
+    }
+    if current_game.language == GameLanguage::Spanish {
+        println!("Ñ is its own letter, words are written without accents.\n");
+    }
+    if matches!(
+        current_game.language,
+        GameLanguage::French | GameLanguage::Italian | GameLanguage::Portuguese
+    ) && !current_game.fold_accents
+    {
+        println!(
+            "Letters with accents are letters of their own, use --fold-accents to type them \
+             without.\n"
+        );
+    }
+    Ok((current_game, possible_words))
+}
+
+// The current game and what's needed to undo guesses
+struct GameState {
+    game: Game,
+    possible_words: Vec<String>,
+    // Game state and possible words before each guess, for undo
+    history: Vec<(Game, Vec<String>)>,
+    // The playfield or word entered for each guess, parallel to the history. Empty for clues
+    // that weren't a guess, like gray letters typed in the command mode.
+    guesses: Vec<String>,
+    // How the words are shown, the possible words are kept in word list order
+    word_order: WordOrder,
+    input_style: InputStyle,
+}
 
-    while possible_words.len() > 1 {
-        let user_input = get_playfield(&current_game, "Enter current playfield");
-        if let Ok(Some(input)) = user_input {
-            current_game.playfield = input.chars().collect();
+impl GameState {
+    fn new(game: Game, possible_words: Vec<String>) -> Self {
+        Self {
+            game,
+            possible_words,
+            history: Vec::new(),
+            guesses: Vec::new(),
+            word_order: WordOrder::default(),
+            input_style: InputStyle::default(),
         }
+    }
 
-        possible_words = solve(&current_game, &possible_words);
+    // Play --first-guess on a new game, returns false if there was none
+    fn play_first_guess(&mut self, args: &cli::Args) -> Result<bool> {
+        let Some((guess, feedback)) = args.first_guess()? else {
+            return Ok(false);
+        };
+        let snapshot = (self.game.clone(), self.possible_words.clone());
+        self.game.apply_feedback(&guess, &feedback)?;
+        self.possible_words = solve(&self.game, &self.possible_words);
+        self.push_guess(snapshot, guess);
+        Ok(true)
+    }
 
-        let user_input = get_chars_not_in_word(&current_game, "Characters not in word?");
-        if let Ok(Some(input)) = user_input {
-            current_game.wrong_letters = input.chars().collect();
+    // Until one word is left, or with keep_going until the user quits
+    const fn keep_narrowing(&self, keep_going: bool) -> bool {
+        self.possible_words.len() > 1 || (keep_going && self.possible_words.len() == 1)
+    }
+
+    fn push_guess(&mut self, snapshot: (Game, Vec<String>), guess: String) {
+        self.history.push(snapshot);
+        self.guesses.push(guess);
+    }
+
+    // Go back to the game before the last guess, returns false if there was no guess to undo
+    fn undo_guess(&mut self) -> bool {
+        let Some((game, possible_words)) = self.history.pop() else {
+            return false;
+        };
+        // Bans aren't guesses, so they are kept
+        let banned_words = std::mem::take(&mut self.game.banned_words);
+        self.game = game;
+        self.game.banned_words = banned_words;
+        self.possible_words = possible_words;
+        self.guesses.pop();
+        true
+    }
+
+    // Number of possible words before each guess and after the last one
+    fn word_counts(&self) -> Vec<usize> {
+        self.history
+            .iter()
+            .map(|(_, possible_words)| possible_words.len())
+            .chain([self.possible_words.len()])
+            .collect()
+    }
+
+    // Print the summary of the game and add it to the stats file if there is one
+    fn finish(&self, answer: Option<&str>, stats_file: Option<&Path>) -> Result<()> {
+        let guesses: Vec<String> = self
+            .guesses
+            .iter()
+            .filter(|guess| !guess.is_empty())
+            .cloned()
+            .collect();
+        println!("{}", output::game_summary(&guesses, answer));
+        if !self.history.is_empty() {
+            println!("\nWords left: {}", sparkline::render(&self.word_counts()));
+        }
+        if let Some(path) = stats_file {
+            let result = GameResult {
+                language: self.game.language,
+                length: self.game.length,
+                guesses: guesses.len(),
+                solved: answer.is_some(),
+            };
+            record_game_result(path, &result)?;
         }
+        Ok(())
+    }
+
+    // After Ctrl-C, print the guesses so far and save the session with --save-on-interrupt
+    fn exit_interrupted(&self, args: &cli::Args) -> Result<()> {
+        // A select prompt hides the cursor until it's done
+        let _ = console::Term::stdout().show_cursor();
+        let guesses: Vec<String> = self
+            .guesses
+            .iter()
+            .filter(|guess| !guess.is_empty())
+            .cloned()
+            .collect();
+        let message = interrupt::save_on_interrupt(
+            args.save_on_interrupt.as_deref(),
+            &self.game,
+            &self.possible_words,
+            &guesses,
+        )?;
+        println!("\n{}", message);
+        std::process::exit(130);
+    }
+}
 
-        clearscreen::clear().expect("Failed to clear screen");
+// The word lists of a game that don't change while it's played
+struct GameWords {
+    // The whole word list for searches that don't use the clues
+    all_words: Vec<String>,
+    // Words that can be guessed, the whole word list unless --guesses is given
+    allowed_guesses: Vec<String>,
+    frequencies: HashMap<String, u64>,
+}
 
-        possible_words = solve(&current_game, &possible_words);
-        println!("All possible words:");
-        print_words(&possible_words, true);
+impl GameWords {
+    fn read(args: &cli::Args, game: &mut Game, possible_words: &[String]) -> Result<Self> {
+        game.guess_source = args.guess_source();
+        let allowed_guesses = if let Some(path) = &args.guesses {
+            let (guesses, skipped) =
+                read_allowed_guesses(path, possible_words, game, args.invalid_words())?;
+            warn_skipped(&skipped);
+            guesses
+        } else {
+            possible_words.to_vec()
+        };
+        let frequencies = args
+            .wordlist
+            .as_deref()
+            .map(read_frequencies)
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Self {
+            all_words: possible_words.to_vec(),
+            allowed_guesses,
+            frequencies,
+        })
+    }
+}
 
-        let possible_words_without_duplicate_letters =
-            filter::words_without_duplicate_letters(&possible_words);
-        print_words(&possible_words_without_duplicate_letters, true);
+fn play_game(args: &cli::Args, resume: Option<&Path>, cache: &mut RunCache) -> Result<()> {
+    let (mut game, possible_words) = start_game(resume, args, cache)?;
+    let words = GameWords::read(args, &mut game, &possible_words)?;
 
-        let possible_words_without_uncommon_letters = filter::words_without_uncommon_letters(
-            &possible_words_without_duplicate_letters,
-            &current_game,
-        );
-        print_words(&possible_words_without_uncommon_letters, true);
+    let mut state = GameState::new(game, possible_words);
+    let skip_input = resume.is_none() && state.play_first_guess(args)?;
+    let _handler = interrupt::Handler::install()?;
+    // A prompt interrupted by Ctrl-C returns an error, the game is saved instead of failing
+    let played = play_turns(&mut state, &words, args, skip_input);
+    if interrupt::interrupted() {
+        return state.exit_interrupted(args);
+    }
+    let gave_up = played?;
+    let answer = state.possible_words.first().filter(|_| !gave_up);
+    state.finish(answer.map(String::as_str), args.stats_file.as_deref())
+}
 
-        let possible_words_with_common_letters = filter::words_with_common_letters(
-            &possible_words_without_uncommon_letters,
-            &current_game,
-        );
+// Returns true if the user gave up
+fn play_turns(
+    state: &mut GameState,
+    words: &GameWords,
+    args: &cli::Args,
+    mut skip_input: bool,
+) -> Result<bool> {
+    let mut gave_up = false;
+    while state.keep_narrowing(args.keep_going) && !interrupt::interrupted() {
+        if !skip_input {
+            let snapshot = (state.game.clone(), state.possible_words.clone());
+            let guess = match state.input_style {
+                InputStyle::Playfield => enter_playfield(&mut state.game)?,
+                InputStyle::WordleCodes => enter_code(&mut state.game, &words.allowed_guesses)?,
+                InputStyle::Positions => enter_positions(&mut state.game)?,
+            };
+            if interrupt::interrupted() {
+                break;
+            }
+            state.push_guess(snapshot, guess);
+        }
+        let words_before = state.possible_words.len();
+        state.possible_words = solve(&state.game, &state.possible_words);
+        if state.possible_words.is_empty() {
+            suggest_corrections(state);
+            let undo = Confirm::new()
+                .with_prompt("No words match these clues, you may have mistyped a letter. Undo?")
+                .default(true)
+                .interact_opt()?
+                .unwrap_or(false);
+            if undo && state.undo_guess() {
+                skip_input = true;
+                continue;
+            }
+            gave_up = true;
+            break;
+        }
+        if !args.quiet {
+            println!(
+                "{} {}\n",
+                output::narrowed_message(words_before, state.possible_words.len()),
+                output::confidence_label(state.possible_words.len())
+            );
+        }
+        export_to_file(args, &state.possible_words)?;
+        if let Some(message) =
+            output::confirmed_message(&state.possible_words).filter(|_| args.keep_going)
+        {
+            println!("{}\n", message);
+        }
+        let shown_words =
+            output::sort_words(&state.possible_words, state.word_order, state.game.language);
+        print_suggestions(&state.game, &shown_words, words, args);
+
+        let can_pick = state.possible_words.len() <= PICK_LIMIT;
+        if let Some(item) = select_menu_item(!state.history.is_empty(), can_pick)? {
+            skip_input = handle_menu_item(item, state, words)?;
+        } else if args.keep_going && state.possible_words.len() == 1 {
+            break;
+        } else {
+            state.possible_words.clear();
+            gave_up = true;
+        }
+        output::clear_screen();
+    }
+    Ok(gave_up)
+}
 
-        println!("\nBest current guesses:");
-        print_words(&possible_words_with_common_letters, false);
+// Same game as play_game, but driven by typed commands instead of menus
+fn play_repl(args: &cli::Args, resume: Option<&Path>, cache: &mut RunCache) -> Result<()> {
+    let (mut game, possible_words) = start_game(resume, args, cache)?;
+    let words = GameWords::read(args, &mut game, &possible_words)?;
 
-        let input = Select::with_theme(&ColorfulTheme::default())
-            .default(0)
-            .item("Update playfield")
-            .item("Show all possible words")
-            .interact_opt()
-            .expect("Should only be able to select index 0 or 1.");
-        if let Some(index) = input {
-            match index {
-                0 => (),
-                1 => {
-                    print_words(&possible_words, false);
-                    // TODO: Make prompt not display [y/n] use another/no library?
-                    Confirm::new()
-                        .with_prompt("Press enter to update playfield")
-                        .default(false)
-                        .report(false)
-                        .show_default(false)
-                        .wait_for_newline(true)
-                        .interact_opt()
-                        .unwrap();
+    let mut state = GameState::new(game, possible_words);
+    if resume.is_none() && state.play_first_guess(args)? {
+        println!(
+            "{} words left after {}.",
+            state.possible_words.len(),
+            state.guesses[0]
+        );
+    }
+    println!("{}\n", repl::HELP);
+    let _handler = interrupt::Handler::install()?;
+    let played = run_commands(&mut state, &words, args);
+    if interrupt::interrupted() {
+        return state.exit_interrupted(args);
+    }
+    let gave_up = played?;
+    let answer = state.possible_words.first().filter(|_| !gave_up);
+    state.finish(answer.map(String::as_str), args.stats_file.as_deref())
+}
+
+// Returns true if the user gave up
+fn run_commands(state: &mut GameState, words: &GameWords, args: &cli::Args) -> Result<bool> {
+    let mut gave_up = false;
+    while state.keep_narrowing(args.keep_going) && !interrupt::interrupted() {
+        let input: String = Input::new()
+            .with_prompt(">")
+            .allow_empty(true)
+            .interact_text()?;
+        match repl::parse_command(&compose_letters(&input)) {
+            Ok(Command::Quit) => {
+                gave_up = state.possible_words.len() != 1;
+                break;
+            }
+            Ok(command) => {
+                if let Err(error) = run_command(command, state, words, args) {
+                    println!("{}", error);
                 }
-                _ => unreachable!(),
             }
-        } else {
-            possible_words.clear();
+            Err(error) => println!("{}", error),
         }
-        clearscreen::clear().expect("Failed to clear screen");
+    }
+    Ok(gave_up)
+}
+
+fn run_command(
+    command: Command,
+    state: &mut GameState,
+    words: &GameWords,
+    args: &cli::Args,
+) -> Result<()> {
+    let snapshot = (state.game.clone(), state.possible_words.clone());
+    let guess = match command {
+        Command::Play(playfield) => {
+            if let Some(letter) = invalid_playfield_letter(state.game.language, &playfield) {
+                bail!("{} is not a letter", letter);
+            }
+            if playfield.chars().count() != state.game.length as usize {
+                bail!("Too few/many letters in playfield");
+            }
+            state.game.playfield = playfield.chars().collect();
+            playfield
+        }
+        Command::Gray(letters) => {
+            state.game.update_wrong_letters(&letters);
+            String::new()
+        }
+        Command::Ban(word) => {
+            state.game.banned_words.insert(word);
+            state.possible_words = solve(&state.game, &state.possible_words);
+            return Ok(());
+        }
+        Command::Undo => {
+            if !state.undo_guess() {
+                bail!("Nothing to undo");
+            }
+            return Ok(());
+        }
+        Command::Words => {
+            print_words(&state.possible_words, false, &state.game);
+            return Ok(());
+        }
+        Command::Contains(letters) => {
+            let letters: Vec<char> = compose_letters(&letters).chars().collect();
+            let found = filter::must_contain(&state.possible_words, &letters);
+            println!("\n{} possible words have them:", found.len());
+            print_words(&found, false, &state.game);
+            return Ok(());
+        }
+        Command::Best => {
+            print_suggestions(&state.game, &state.possible_words, words, args);
+            return Ok(());
+        }
+        Command::Regret(word) => {
+            let word = compose_letters(&word);
+            if word.chars().count() != state.game.length as usize {
+                bail!("Too few/many letters in guess");
+            }
+            print_regret(&state.game, &word, &state.possible_words, words);
+            return Ok(());
+        }
+        Command::Help => {
+            println!("{}", repl::HELP);
+            return Ok(());
+        }
+        Command::Quit => return Ok(()),
+    };
+    state.game.update_letter_counts();
+    state.game.record_misplaced_letters();
+    let words_before = state.possible_words.len();
+    state.possible_words = solve(&state.game, &state.possible_words);
+    state.push_guess(snapshot, guess);
+    if state.possible_words.is_empty() {
+        state.undo_guess();
+        bail!("No words match these clues, you may have mistyped a letter");
+    }
+    if !args.quiet {
+        println!(
+            "{} {}",
+            output::narrowed_message(words_before, state.possible_words.len()),
+            output::confidence_label(state.possible_words.len())
+        );
+    }
+    export_to_file(args, &state.possible_words)?;
+    if let Some(message) =
+        output::confirmed_message(&state.possible_words).filter(|_| args.keep_going)
+    {
+        println!("{}", message);
     }
     Ok(())
 }
 
-fn get_playfield(game: &Game, prompt: &str) -> Result<Option<String>> {
-    let input: String = Input::new()
+// Playfields one letter away from the last one entered that would leave some words
+fn suggest_corrections(state: &GameState) {
+    let (Some((game, possible_words)), Some(entered)) =
+        (state.history.last(), state.guesses.last())
+    else {
+        return;
+    };
+    let mut game = game.clone();
+    game.wrong_letters.clone_from(&state.game.wrong_letters);
+    let playfield: Vec<char> = entered.chars().collect();
+    // Pasted feedback rows are saved as the guessed word, which has no green or empty slots
+    if playfield.len() != game.length as usize || playfield.iter().all(|c| c.is_lowercase()) {
+        return;
+    }
+    for (corrected, count) in playfield_corrections(&game, &playfield, possible_words)
+        .into_iter()
+        .take(3)
+    {
+        println!(
+            "Did you mean {} instead of {}? ({} words)",
+            corrected, entered, count
+        );
+    }
+}
+
+// Returns true if the playfield shouldn't be entered before the words are shown again
+fn handle_menu_item(item: MenuItem, state: &mut GameState, words: &GameWords) -> Result<bool> {
+    let all_words = words.all_words.as_slice();
+    match item {
+        MenuItem::UpdatePlayfield => return Ok(false),
+        MenuItem::ShowAllWords => {
+            let shown_words =
+                output::sort_words(&state.possible_words, state.word_order, state.game.language);
+            show_all_words(&state.game, &shown_words);
+            return Ok(false);
+        }
+        MenuItem::PickWord => {
+            let snapshot = (state.game.clone(), state.possible_words.clone());
+            let shown_words =
+                output::sort_words(&state.possible_words, state.word_order, state.game.language);
+            let Some(guess) = pick_word(&mut state.game, &shown_words)? else {
+                return Ok(false);
+            };
+            state.push_guess(snapshot, guess);
+        }
+        MenuItem::PasteFeedback => {
+            let snapshot = (state.game.clone(), state.possible_words.clone());
+            let Some(guess) = paste_feedback_row(&mut state.game, &words.allowed_guesses)? else {
+                return Ok(false);
+            };
+            state.push_guess(snapshot, guess);
+        }
+        MenuItem::AnalyzeGuess => analyze_guess(&state.game, &state.possible_words, words)?,
+        MenuItem::ExplainWord => explain_word(&state.game, all_words)?,
+        MenuItem::LettersBySlot => show_letters_by_slot(&state.game, &state.possible_words),
+        MenuItem::PatternSearch => search_words(&state.game, all_words)?,
+        MenuItem::BanWord => {
+            if let Some(word) = prompt_guess(&state.game, "Word to ban")? {
+                state.game.banned_words.insert(word);
+            }
+        }
+        MenuItem::RepeatedLetters => {
+            let letters: String = Input::new()
+                .with_prompt(
+                    "Letters repeated as many times as they occur, like rr, or =e for exactly",
+                )
+                .allow_empty(true)
+                .interact_text()?;
+            state.game.require_letters(&compose_letters(&letters));
+        }
+        MenuItem::WordOrder => {
+            state.word_order = state.word_order.next();
+            println!("Showing words in {}.", state.word_order.label());
+        }
+        MenuItem::InputStyle => {
+            state.input_style = state.input_style.next();
+            println!("Input style: {}", state.input_style.label());
+        }
+        MenuItem::Restart => {
+            state.game.reset();
+            state.possible_words = all_words.to_vec();
+            state.history.clear();
+            state.guesses.clear();
+            return Ok(false);
+        }
+        MenuItem::Undo => return Ok(state.undo_guess()),
+        MenuItem::Help => show_tutorial(),
+        MenuItem::ExportWords => export_words(&state.possible_words)?,
+        MenuItem::SaveAndQuit => save_and_quit(&state.game, &state.possible_words)?,
+    }
+    Ok(true)
+}
+
+// Returns the playfield as entered, before yellow letters are moved out of it
+fn enter_playfield(game: &mut Game) -> Result<String> {
+    if let Some(input) = get_playfield(game, "Enter current playfield")? {
+        game.playfield = input.chars().collect();
+    }
+    let entered = game.playfield.iter().collect();
+    if interrupt::interrupted() {
+        return Ok(entered);
+    }
+
+    if let Some(input) = get_chars_not_in_word(game, "Characters not in word?")? {
+        game.update_wrong_letters(&input);
+    }
+
+    output::clear_screen();
+
+    game.update_letter_counts();
+    game.record_misplaced_letters();
+    Ok(entered)
+}
+
+// Returns the guess, the game is unchanged if it was left empty
+fn enter_code(game: &mut Game, dictionary: &[String]) -> Result<String> {
+    let Some(guess) = prompt_guess(game, "Guessed word")? else {
+        return Ok(String::new());
+    };
+    warn_unknown_guess(&guess, dictionary);
+    let code: String = Input::new()
+        .with_prompt("Code (G green, Y yellow, B gray)")
+        .validate_with(|user_input: &String| -> Result<(), String> {
+            parse_code_feedback(&guess, user_input)
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        })
+        .interact_text()?;
+    let feedback = parse_code_feedback(&guess, &code)?;
+    game.apply_feedback(&guess, &feedback)?;
+    output::clear_screen();
+    Ok(guess)
+}
+
+// Returns the green letters as entered
+fn enter_positions(game: &mut Game) -> Result<String> {
+    let (language, length) = (game.language, game.length);
+    let green: String = Input::new()
+        .with_prompt("Green letters in their slots, - if not green")
+        .with_initial_text(game.playfield.iter().collect::<String>().to_lowercase())
+        .validate_with(|user_input: &String| -> Result<(), String> {
+            let green = compose_letters(user_input);
+            if let Some(letter) = invalid_playfield_letter(language, &green) {
+                return Err(format!("{} is not a letter", letter));
+            }
+            if green.chars().count() == length as usize {
+                Ok(())
+            } else {
+                Err("Too few/many letters in green letters".to_string())
+            }
+        })
+        .interact_text()?;
+    let yellow: String = Input::new()
+        .with_prompt("Yellow letters and the slots they were in, like t:4 r:1,3")
+        .allow_empty(true)
+        .validate_with(|user_input: &String| -> Result<(), String> {
+            cli::parse_present(&compose_letters(user_input), language, length)
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        })
+        .interact_text()?;
+    if let Some(input) = get_chars_not_in_word(game, "Characters not in word?")? {
+        game.update_wrong_letters(&input);
+    }
+    output::clear_screen();
+
+    let mut green = compose_letters(green.trim_matches('\n'));
+    let mut yellow = compose_letters(&yellow);
+    if game.fold_accents {
+        green = fold_accents(&green);
+        yellow = fold_accents(&yellow);
+    }
+    game.set_positions(&green, &cli::parse_present(&yellow, language, length)?)?;
+    Ok(green.to_uppercase())
+}
+
+// Undo is only offered when there is a guess to undo
+// Fails on Ctrl-C, which the game loop handles
+fn select_menu_item(can_undo: bool, can_pick: bool) -> Result<Option<MenuItem>> {
+    let mut menu = vec![MenuItem::UpdatePlayfield];
+    if can_pick {
+        menu.push(MenuItem::PickWord);
+    }
+    menu.extend([
+        MenuItem::ShowAllWords,
+        MenuItem::PasteFeedback,
+        MenuItem::AnalyzeGuess,
+        MenuItem::ExplainWord,
+        MenuItem::LettersBySlot,
+        MenuItem::PatternSearch,
+        MenuItem::BanWord,
+        MenuItem::RepeatedLetters,
+        MenuItem::ExportWords,
+        MenuItem::WordOrder,
+        MenuItem::InputStyle,
+        MenuItem::Restart,
+    ]);
+    if can_undo {
+        menu.push(MenuItem::Undo);
+    }
+    menu.push(MenuItem::Help);
+    menu.push(MenuItem::SaveAndQuit);
+    let selected = Select::with_theme(&ColorfulTheme::default())
+        .default(0)
+        .items(&menu.iter().map(|item| item.label()).collect::<Vec<_>>())
+        .interact_opt()?;
+    Ok(selected.map(|index| menu[index]))
+}
+
+fn show_all_words(game: &Game, possible_words: &[String]) {
+    print_words(possible_words, false, game);
+    wait_for_enter("Press enter to update playfield");
+}
+
+fn wait_for_enter(prompt: &str) {
+    // TODO: Make prompt not display [y/n] use another/no library?
+    // Fails on Ctrl-C, which the game loop handles
+    let _ = Confirm::new()
         .with_prompt(prompt)
-        .with_initial_text(game.playfield.iter().collect::<String>())
+        .default(false)
+        .report(false)
+        .show_default(false)
+        .wait_for_newline(true)
+        .interact_opt();
+}
+
+// Show how many words would be left for each feedback a guess could get
+fn analyze_guess(game: &Game, possible_words: &[String], words: &GameWords) -> Result<()> {
+    let guess: String = Input::new()
+        .with_prompt("Word to analyze")
         .validate_with(|user_input: &String| -> Result<(), &str> {
-            if user_input.chars().count() == game.length as usize {
+            if compose_letters(user_input.trim()).chars().count() == game.length as usize {
                 Ok(())
             } else {
-                Err("To few/many letters in playfield")
+                Err("Too few/many letters in guess")
             }
         })
         .interact_text()?;
+    let guess = compose_letters(guess.trim());
+    let mut distribution: Vec<(String, usize)> =
+        filter::outcome_distribution(&guess, possible_words)
+            .into_iter()
+            .collect();
+    distribution.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    for (pattern, count) in distribution {
+        println!("{}\t{}", pattern, count);
+    }
+    print_regret(game, &guess, possible_words, words);
+    wait_for_enter("Press enter to continue");
+    Ok(())
+}
 
-    Ok(Some(input.trim_matches('\n').to_string().replace(' ', "-")))
+// How much less the guess tells than the best guess that could be played instead
+fn print_regret(game: &Game, guess: &str, possible_words: &[String], words: &GameWords) {
+    let guess_pool = filter::guess_pool(game, possible_words, &words.allowed_guesses);
+    let progress = output::ProgressBar::new("Ranking guesses", guess_pool.len());
+    let (best_guess, bits) = filter::regret(guess, possible_words, &guess_pool, |done, total| {
+        progress.update(done, total);
+    });
+    drop(progress);
+    println!("{}", output::regret_message(&best_guess, bits));
 }
 
-fn get_chars_not_in_word(game: &Game, prompt: &str) -> Result<Option<String>> {
-    let input: String = Input::new()
-        .with_prompt(prompt)
+fn explain_word(game: &Game, all_words: &[String]) -> Result<()> {
+    let Some(word) = prompt_guess(game, "Word that isn't shown")? else {
+        return Ok(());
+    };
+    match filter::explain_elimination(&word, game) {
+        Some(reason) => println!("{} {}", word.to_uppercase(), reason),
+        None if all_words.contains(&word) => {
+            println!("{} matches all the clues and is shown", word.to_uppercase());
+        }
+        None => println!(
+            "{} matches all the clues but isn't in the word list",
+            word.to_uppercase()
+        ),
+    }
+    wait_for_enter("Press enter to continue");
+    Ok(())
+}
+
+fn show_tutorial() {
+    let feedback = compute_feedback("slate", "sandy");
+    println!("\n{}", output::tutorial("sandy", "slate", &feedback));
+    wait_for_enter("Press enter to continue");
+}
+
+fn show_letters_by_slot(game: &Game, possible_words: &[String]) {
+    let frequencies = filter::positional_frequencies(possible_words, game.length as usize);
+    print!("\n{}", output::positional_table(&frequencies, 8));
+    wait_for_enter("Press enter to continue");
+}
+
+fn search_words(game: &Game, words: &[String]) -> Result<()> {
+    let pattern: String = Input::new()
+        .with_prompt("Pattern, _ for any letter")
+        .with_initial_text("_".repeat(game.length as usize))
+        .interact_text()?;
+    let required: String = Input::new()
+        .with_prompt("Letters the word must contain")
         .allow_empty(true)
-        .with_initial_text(game.wrong_letters.iter().collect::<String>())
         .interact_text()?;
+    let required: Vec<char> = compose_letters(required.trim()).chars().collect();
+    let found = filter::pattern_search(words, &compose_letters(pattern.trim()), &required);
+    println!("\nFound {} words:", found.len());
+    print_words(&found, false, game);
+    wait_for_enter("Press enter to continue");
+    Ok(())
+}
 
-    let trimmed_input = input.trim().to_lowercase();
+fn export_words(possible_words: &[String]) -> Result<()> {
+    let path: String = Input::new()
+        .with_prompt("Export possible words to")
+        .default("wordlehelper-words.txt".to_string())
+        .interact_text()?;
+    let path = Path::new(&path);
+    write_words(path, possible_words)?;
+    println!("{}", output::exported_message(possible_words.len(), path));
+    wait_for_enter("Press enter to continue");
+    Ok(())
+}
 
-    if trimmed_input.is_empty() {
-        Ok(None)
-    } else {
-        Ok(Some(trimmed_input))
-    }
+fn save_and_quit(game: &Game, possible_words: &[String]) -> Result<()> {
+    let path: String = Input::new()
+        .with_prompt("Save session to")
+        .default("wordlehelper.session".to_string())
+        .interact_text()?;
+    save_session(Path::new(&path), game, possible_words)?;
+    println!("Saved, continue with --resume {}", path);
+    std::process::exit(0);
 }
 
-fn solve(game: &Game, possible_words: &[String]) -> Vec<String> {
-    let mut new_possible_words: Vec<String> = Vec::with_capacity(4096);
-    'nextword: for word in possible_words {
-        // Ignore words without known correct characters in correct slot
-        for (index, letter) in word.chars().enumerate() {
-            if game.playfield[index].is_uppercase()
-                && letter.to_string() != game.playfield[index].to_lowercase().to_string()
-            {
-                continue 'nextword;
-            }
+fn print_suggestions(game: &Game, possible_words: &[String], words: &GameWords, args: &cli::Args) {
+    let frequencies = &words.frequencies;
+    // A few words left, so guessing the answer beats learning more
+    if possible_words.len() <= 3 {
+        if let Some(word) = filter::final_guess(possible_words, game, frequencies) {
+            println!(
+                "{}\n",
+                console::style(format!("Play {} now.", word.to_uppercase())).bold()
+            );
+        }
+    }
+    println!("All possible words:");
+    print_words(possible_words, true, game);
+    if let Some((pattern, cluster)) =
+        filter::largest_cluster(possible_words).filter(|_| !args.quiet)
+    {
+        println!(
+            "{}\n",
+            console::style(output::cluster_warning(&pattern, &cluster)).yellow()
+        );
+    }
 
-            if (game.playfield[index].is_lowercase()) && !word.contains(game.playfield[index])
-                || letter == game.playfield[index]
-            {
-                continue 'nextword;
-            }
+    let suggestions = filter::suggest(game, possible_words, args.tie_break(), args.keep_duplicates);
+    // The best guesses are printed below with their scores
+    let steps = [
+        (
+            suggestions.no_duplicates.as_ref(),
+            "Filtering out words with duplicate letters...",
+            true,
+        ),
+        (
+            Some(&suggestions.no_uncommon),
+            "Filtering out possible words with uncommon letters...",
+            true,
+        ),
+        (
+            Some(&suggestions.best_guesses),
+            "Filtering out words with the highest amount of common letters...",
+            false,
+        ),
+    ];
+    for (result, message, show_words) in steps {
+        let Some(result) = result else {
+            continue;
+        };
+        if let Some(message) = output::filter_message(result, message, args.quiet) {
+            println!("{}", message);
+        }
+        if !args.quiet && show_words {
+            print_words(&result.words, true, game);
+        }
+    }
 
-            // Ignore words with letters that is known to not be in the word unless part of a locked match,
-            // or if the letter is known to be somewhere in the word but currently in the wrong slot.
-            if game.wrong_letters.iter().any(|&c| c == letter)
-                && letter.to_uppercase().to_string() != game.playfield[index].to_string()
-                && !game.playfield.iter().any(|&c| c == letter)
-            {
-                continue 'nextword;
-            }
+    println!("\nBest current guesses:");
+    let best_guesses: Vec<(String, String)> = if frequencies.is_empty() {
+        // They all have as many common letters, the ones covering more vowels come first
+        let mut best_guesses = suggestions.best_guesses.words.clone();
+        best_guesses.sort_by_cached_key(|word| Reverse(filter::distinct_vowel_count(word, game)));
+        best_guesses
+            .into_iter()
+            .map(|word| {
+                let count = filter::common_letter_count(&word, game);
+                let vowels = filter::distinct_vowel_count(&word, game);
+                let score = format!("{} common letters, {} vowels", count, vowels);
+                (word, score)
+            })
+            .collect()
+    } else {
+        // Common words are more likely answers, so mix in how common they are
+        filter::blend_frequency_with_common_letters(
+            &suggestions.no_uncommon.words,
+            game,
+            frequencies,
+        )
+        .into_iter()
+        .take(10)
+        .map(|(word, score)| (word, format!("score {:.2}", score)))
+        .collect()
+    };
+    print_ranked_words(&best_guesses, game, args.scores);
+    if args.coverage {
+        for (word, _) in &best_guesses {
+            let coverage = filter::letter_coverage(word, possible_words);
+            println!("{}", output::coverage_message(word, coverage));
         }
+    }
+    print_strategy_guesses(game, possible_words, words, args);
 
-        new_possible_words.push(word.to_string());
+    if !args.quiet {
+        println!("\n{}", output::keyboard(game));
+        println!(
+            "\n{}",
+            output::unknown_letters_message(&game.unknown_letters())
+        );
     }
-    new_possible_words
 }
 
-fn print_words(words: &[String], limit: bool) {
-    let word_count = words.len();
-    let chunk_size = match word_count {
-        x if x < 6 => 3,
-        x if x < 20 => 4,
-        x if x < 40 => 5,
-        _ => 6,
+// The best guesses by the --strategy scoring
+fn print_strategy_guesses(
+    game: &Game,
+    possible_words: &[String],
+    words: &GameWords,
+    args: &cli::Args,
+) {
+    let guess_pool = filter::guess_pool(game, possible_words, &words.allowed_guesses);
+    let progress = output::ProgressBar::new("Ranking guesses", guess_pool.len());
+    let update_progress = |done, total| progress.update(done, total);
+    let ranked = filter::rank_with_strategy(
+        args.strategy.scoring(),
+        possible_words,
+        &guess_pool,
+        game,
+        update_progress,
+    );
+    drop(progress);
+    let heading = match args.strategy {
+        Strategy::Entropy => "Best guesses by expected information",
+        Strategy::Minimax => "Best guesses by smallest worst case",
+        Strategy::CommonLetters => "Best guesses by common letters",
+        Strategy::Vowels => "Best guesses by common letters and vowels",
+        Strategy::Fresh => "Best guesses by letters not tried yet",
     };
-    if limit && word_count > 30 {
-        println!("To many words to print ({}).", word_count);
+    let ranked_words: Vec<(String, String)> = ranked
+        .into_iter()
+        .take(6)
+        .map(|(word, score)| {
+            let score = match args.strategy {
+                Strategy::Entropy => format!("{:.2} bits", score),
+                Strategy::Minimax => format!("at most {} left", -score),
+                Strategy::CommonLetters => format!("{} common letters", score),
+                Strategy::Vowels => format!(
+                    "{} common letters, {} vowels",
+                    filter::common_letter_count(&word, game),
+                    filter::distinct_vowel_count(&word, game)
+                ),
+                Strategy::Fresh => format!("{} new letters", score),
+            };
+            (word, score)
+        })
+        .collect();
+    println!("\n{}:", heading);
+    print_ranked_words(&ranked_words, game, args.scores);
+}
+
+fn print_ranked_words(words: &[(String, String)], game: &Game, show_scores: bool) {
+    if show_scores {
+        print_scored_words(words, game);
     } else {
-        println!();
-        for chunk in words.chunks(chunk_size) {
-            for string in chunk {
-                print!("{}\t\t", string);
-            }
-            println!();
-            if word_count < 20 {
-                println!();
+        let words: Vec<String> = words.iter().map(|(word, _)| word.clone()).collect();
+        print_words(&words, false, game);
+    }
+}
+
+// Returns the guess, or None if it was left empty and the playfield should be entered instead
+fn paste_feedback_row(game: &mut Game, dictionary: &[String]) -> Result<Option<String>> {
+    let Some(guess) = prompt_guess(game, "Guessed word")? else {
+        return Ok(None);
+    };
+    warn_unknown_guess(&guess, dictionary);
+    let feedback = prompt_feedback(&guess, "Feedback row (🟩🟨⬛)")?;
+    game.apply_feedback(&guess, &feedback)?;
+    Ok(Some(guess))
+}
+
+// Returns the picked guess, or None if no word was picked
+fn pick_word(game: &mut Game, possible_words: &[String]) -> Result<Option<String>> {
+    let items: Vec<String> = possible_words
+        .iter()
+        .map(|word| word.to_uppercase())
+        .collect();
+    let Some(index) = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Word to guess")
+        .default(0)
+        .items(&items)
+        .interact_opt()?
+    else {
+        return Ok(None);
+    };
+    let guess = possible_words[index].clone();
+    let was_answer = Confirm::new()
+        .with_prompt(format!("Was {} the answer?", items[index]))
+        .default(true)
+        .interact()?;
+    let feedback = if was_answer {
+        None
+    } else {
+        Some(prompt_feedback(&guess, "Feedback row (🟩🟨⬛)")?)
+    };
+    game.apply_pick(&guess, feedback.as_deref())?;
+    Ok(Some(guess))
+}
+
+// None if the guess was left empty
+fn prompt_guess(game: &Game, prompt: &str) -> Result<Option<String>> {
+    let guess: String = Input::new()
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .validate_with(|user_input: &String| -> Result<(), &str> {
+            let length = compose_letters(user_input.trim()).chars().count();
+            if length == 0 || length == game.length as usize {
+                Ok(())
+            } else {
+                Err("Too few/many letters in guess")
             }
-        }
+        })
+        .interact_text()?;
+    let guess = compose_letters(guess.trim()).to_lowercase();
+    Ok(Some(guess).filter(|guess| !guess.is_empty()))
+}
+
+// The guess is still used, the word list could be missing words
+fn warn_unknown_guess(guess: &str, dictionary: &[String]) {
+    if let Some(warning) = output::unknown_guess_message(guess, dictionary) {
+        println!("{}", console::style(warning).yellow());
     }
 }
 
-fn read_file(game: &Game) -> Result<Vec<String>> {
-    let file: fs::File = if matches!(game.language, GameLanguage::English) {
-        fs::File::open("english5.txt")?
+fn prompt_feedback(guess: &str, prompt: &str) -> Result<Vec<Feedback>> {
+    let pattern: String = Input::new()
+        .with_prompt(prompt)
+        .validate_with(|user_input: &String| -> Result<(), String> {
+            parse_emoji_feedback(guess, user_input)
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        })
+        .interact_text()?;
+    parse_emoji_feedback(guess, &pattern)
+}
+
+fn get_playfield(game: &Game, prompt: &str) -> Result<Option<String>> {
+    let input: String = retry_prompt(|| {
+        Input::new()
+            .with_prompt(prompt)
+            .with_initial_text(game.playfield.iter().collect::<String>())
+            .validate_with(|user_input: &String| -> Result<(), String> {
+                let playfield = compose_letters(user_input);
+                if let Some(letter) = invalid_playfield_letter(game.language, &playfield) {
+                    return Err(format!("{} is not a letter", letter));
+                }
+                if playfield.chars().count() == game.length as usize {
+                    Ok(())
+                } else {
+                    Err("Too few/many letters in playfield".to_string())
+                }
+            })
+            .interact_text()
+    })?;
+
+    let playfield = compose_letters(input.trim_matches('\n')).replace(' ', "-");
+    Ok(Some(if game.fold_accents {
+        fold_accents(&playfield)
     } else {
-        match game.length {
-            GameLength::Six => fs::File::open("svenska6.txt")?,
-            GameLength::Five => fs::File::open("svenska5.txt")?,
-        }
+        playfield
+    }))
+}
+
+// A failed prompt is asked again, unless it failed because of Ctrl-C
+fn retry_prompt<T>(prompt: impl FnMut() -> dialoguer::Result<T>) -> dialoguer::Result<T> {
+    output::retry_input(INPUT_ATTEMPTS, |_| !interrupt::interrupted(), prompt)
+}
+
+// The letters are added to the ones entered before, which are shown in the prompt
+fn get_chars_not_in_word(game: &Game, prompt: &str) -> Result<Option<String>> {
+    let prompt = if game.wrong_letters.is_empty() {
+        prompt.to_string()
+    } else {
+        format!(
+            "{} ({} so far, -x removes x)",
+            prompt,
+            game.wrong_letters.iter().collect::<String>()
+        )
     };
-    let possible_words: Vec<String> = io::BufReader::new(file)
-        .lines()
-        .collect::<io::Result<_>>()?;
-    Ok(possible_words)
+    let input: String = retry_prompt(|| {
+        Input::new()
+            .with_prompt(&prompt)
+            .allow_empty(true)
+            .interact_text()
+    })?;
+
+    let mut trimmed_input = compose_letters(input.trim()).to_lowercase();
+    if game.fold_accents {
+        trimmed_input = fold_accents(&trimmed_input);
+    }
+
+    if trimmed_input.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(trimmed_input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_game_after_resume() {
+        let dir = std::env::temp_dir();
+        let wordlist = dir.join("wordlehelper_test_resume_words.txt");
+        let session = dir.join("wordlehelper_test_resume_session.txt");
+        let words = ["crane", "slate", "mound", "pious", "baker"].map(String::from);
+        std::fs::write(&wordlist, words.join("\n")).unwrap();
+        let args = cli::Args {
+            default_language: Some(GameLanguage::English),
+            use_defaults: true,
+            quiet: true,
+            wordlist: Some(wordlist.clone()),
+            ..cli::Args::default()
+        };
+        let resumed = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        save_session(&session, &resumed, &["mound".to_string()]).unwrap();
+
+        let mut cache = RunCache::default();
+        let (_, resumed_words) = start_game(Some(&session), &args, &mut cache).unwrap();
+        assert_eq!(resumed_words, ["mound"]);
+        assert!(cache.opening_words.is_empty());
+        assert!(cache.opening_pairs.is_empty());
+
+        let (game, possible_words) = start_game(None, &args, &mut cache).unwrap();
+        assert_eq!(possible_words, words);
+        let key = (GameLanguage::English, GameLength::Five);
+        assert_eq!(
+            cache.opening_words[&key],
+            filter::best_opening_word(&words, &game)
+        );
+        assert_eq!(
+            cache.opening_pairs[&key],
+            filter::best_opening_pair(&words, &game)
+        );
+        std::fs::remove_file(&wordlist).unwrap();
+        std::fs::remove_file(&session).unwrap();
+    }
 }
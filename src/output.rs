@@ -0,0 +1,1095 @@
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    fmt::{Display, Write},
+    ops::Range,
+    path::Path,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use console::{style, Key, Term};
+use wordlehelper::{
+    feedback::Feedback, filter::FilterResult, fold_letter, simulate::BenchmarkReport,
+    wordlist::EMBEDDED_LISTS, Game, GameLanguage, LetterState,
+};
+
+static MAX_PRINT: AtomicUsize = AtomicUsize::new(30);
+
+// Set once from --max-print
+pub fn set_max_print(max_print: usize) {
+    MAX_PRINT.store(max_print, Ordering::Relaxed);
+}
+
+// With limit set, only the count is printed when there are more words than --max-print. In a
+// terminal, words that don't fit on the screen are shown a page at a time.
+pub fn print_words(words: &[String], limit: bool, game: &Game) {
+    let word_count = words.len();
+    let terminal_size = Term::stdout().size_checked();
+    let chunk_size = terminal_size.map_or_else(
+        || default_column_count(word_count),
+        |(_, columns)| column_count(usize::from(columns), longest_word(words)),
+    );
+    if let Some(message) =
+        too_many_words_message(word_count, limit.then(|| MAX_PRINT.load(Ordering::Relaxed)))
+    {
+        println!("{}", message);
+        return;
+    }
+    let mut lines = Vec::new();
+    for row in format_rows(words, chunk_size, game) {
+        lines.push(row);
+        if word_count < 20 {
+            lines.push(String::new());
+        }
+    }
+    // One line of the screen is left for the prompt
+    let page_size = terminal_size.map(|(rows, _)| usize::from(rows).saturating_sub(1));
+    let pages = page_ranges(lines.len(), page_size);
+    println!();
+    for (index, page) in pages.iter().enumerate() {
+        for line in &lines[page.clone()] {
+            println!("{}", line);
+        }
+        if index + 1 < pages.len() && !wait_for_next_page(index + 1, pages.len()) {
+            break;
+        }
+    }
+}
+
+// The lines on each page, all of them on one page without a page size
+fn page_ranges(line_count: usize, page_size: Option<usize>) -> Vec<Range<usize>> {
+    let page_size = page_size.unwrap_or(line_count).max(1);
+    (0..line_count)
+        .step_by(page_size)
+        .map(|start| start..(start + page_size).min(line_count))
+        .collect()
+}
+
+// Returns false if the rest of the pages should be skipped, on q or <Esc>
+fn wait_for_next_page(page: usize, page_count: usize) -> bool {
+    let term = Term::stdout();
+    let _ = term.write_str(&format!(
+        "-- Page {} of {}, press any key for more or q to stop --",
+        page, page_count
+    ));
+    let key = term.read_key();
+    let _ = term.clear_line();
+    !matches!(key, Ok(Key::Char('q') | Key::Escape) | Err(_))
+}
+
+// Without a terminal, like when piped, more columns are used the more words there are
+const fn default_column_count(word_count: usize) -> usize {
+    match word_count {
+        x if x < 6 => 3,
+        x if x < 20 => 4,
+        x if x < 40 => 5,
+        _ => 6,
+    }
+}
+
+// As many columns as fit in the terminal, with the gap between columns but not after the last
+const fn column_count(terminal_width: usize, word_length: usize) -> usize {
+    let columns = (terminal_width + COLUMN_GAP) / (word_length + COLUMN_GAP);
+    if columns == 0 {
+        1
+    } else {
+        columns
+    }
+}
+
+fn too_many_words_message(word_count: usize, max_print: Option<usize>) -> Option<String> {
+    max_print
+        .filter(|&max_print| word_count > max_print)
+        .map(|_| format!("Too many words to print ({}).", word_count))
+}
+
+// One word per line with its score after it, like "slate (4 common letters)"
+pub fn print_scored_words(words: &[(String, String)], game: &Game) {
+    println!();
+    for (word, score) in words {
+        println!("{}", format_scored_word(word, score, game));
+    }
+    println!();
+}
+
+fn format_scored_word(word: &str, score: &str, game: &Game) -> String {
+    format!("{} ({})", highlight_letters(word, game), score)
+}
+
+// Pad every word to the longest one plus a gap so the columns line up in all rows. The width
+// is counted in chars, so words with å, ä and ö get the same padding as other words.
+fn format_rows(words: &[String], chunk_size: usize, game: &Game) -> Vec<String> {
+    let width = longest_word(words) + COLUMN_GAP;
+    words
+        .chunks(chunk_size)
+        .map(|chunk| {
+            let mut row = String::new();
+            for (index, word) in chunk.iter().enumerate() {
+                row.push_str(&highlight_letters(word, game));
+                if index + 1 < chunk.len() {
+                    row.push_str(&" ".repeat(width - word.chars().count()));
+                }
+            }
+            row
+        })
+        .collect()
+}
+
+const COLUMN_GAP: usize = 4;
+
+fn longest_word(words: &[String]) -> usize {
+    words
+        .iter()
+        .map(|word| word.chars().count())
+        .max()
+        .unwrap_or(0)
+}
+
+// Letters locked in place in green and other letters known to be in the word in yellow. Colors
+// are left out when turned off or when stdout isn't a terminal.
+fn highlight_letters(word: &str, game: &Game) -> String {
+    word.chars()
+        .enumerate()
+        .map(|(index, letter)| {
+            let compared = if game.fold_accents {
+                fold_letter(letter)
+            } else {
+                letter
+            };
+            let lower_case: String = compared.to_lowercase().collect();
+            let slot = game.playfield.get(index).copied().unwrap_or('-');
+            if slot.is_uppercase() && slot.to_lowercase().eq(compared.to_lowercase()) {
+                style(letter).green().bold().to_string()
+            } else if lower_case.chars().any(|c| game.is_known_letter(c)) {
+                style(letter).yellow().to_string()
+            } else {
+                letter.to_string()
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum WordOrder {
+    // The order of the word list
+    #[default]
+    List,
+    Alphabetical,
+    // Shortest first, alphabetical within the same length
+    Length,
+}
+
+impl WordOrder {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::List => Self::Alphabetical,
+            Self::Alphabetical => Self::Length,
+            Self::Length => Self::List,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::List => "word list order",
+            Self::Alphabetical => "alphabetical order",
+            Self::Length => "order of length",
+        }
+    }
+}
+
+// Words in the given order, words that compare equal keep their order
+pub fn sort_words(words: &[String], order: WordOrder, language: GameLanguage) -> Vec<String> {
+    let mut sorted = words.to_vec();
+    match order {
+        WordOrder::List => {}
+        WordOrder::Alphabetical => sorted.sort_by_cached_key(|word| collation_key(word, language)),
+        WordOrder::Length => {
+            sorted.sort_by_cached_key(|word| (word.chars().count(), collation_key(word, language)));
+        }
+    }
+    sorted
+}
+
+// Sorts words the way the language's dictionaries do. Swedish å, ä and ö come after z and
+// Spanish ñ after n, while other accented letters sort with their letter and only decide the
+// order of words that are otherwise the same.
+fn collation_key(word: &str, language: GameLanguage) -> (Vec<u32>, String) {
+    let word = word.to_lowercase();
+    let primary = word
+        .chars()
+        .map(|letter| match (language, letter) {
+            (GameLanguage::Swedish, 'å') => u32::from('z') * 2 + 2,
+            (GameLanguage::Swedish, 'ä') => u32::from('z') * 2 + 4,
+            (GameLanguage::Swedish, 'ö') => u32::from('z') * 2 + 6,
+            (GameLanguage::Spanish, 'ñ') => u32::from('n') * 2 + 1,
+            (_, 'ß') => u32::from('s') * 2,
+            _ => u32::from(fold_letter(letter)) * 2,
+        })
+        .collect();
+    (primary, word)
+}
+
+// One row per slot with the most common letters in that slot and their counts
+pub fn positional_table(frequencies: &[HashMap<char, usize>], letters_per_slot: usize) -> String {
+    let mut table = String::new();
+    for (slot, counts) in frequencies.iter().enumerate() {
+        let mut counts: Vec<(char, usize)> = counts.iter().map(|(&c, &n)| (c, n)).collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let row: Vec<String> = counts
+            .iter()
+            .take(letters_per_slot)
+            .map(|(letter, count)| format!("{} {:<4}", letter.to_uppercase(), count))
+            .collect();
+        let _ = writeln!(table, "{}: {}", slot + 1, row.join(" ").trim_end());
+    }
+    table
+}
+
+// The answer, or that the game was given up, followed by the guesses
+pub fn game_summary(guesses: &[String], answer: Option<&str>) -> String {
+    let count = match guesses.len() {
+        1 => "1 guess".to_string(),
+        count => format!("{} guesses", count),
+    };
+    let mut summary = answer.map_or_else(
+        || format!("Gave up after {}.", count),
+        |answer| format!("Solved in {}: {}", count, answer.to_uppercase()),
+    );
+    for (index, guess) in guesses.iter().enumerate() {
+        let _ = write!(summary, "\n{}. {}", index + 1, guess);
+    }
+    summary
+}
+
+// Colors are on where the terminal supports them, unless --no-color is given or NO_COLOR is set
+// to anything but an empty string (no-color.org)
+pub fn colors_enabled(no_color: bool, no_color_env: Option<&OsStr>, terminal_colors: bool) -> bool {
+    terminal_colors && !no_color && no_color_env.is_none_or(OsStr::is_empty)
+}
+
+static CLEAR_SCREEN: AtomicBool = AtomicBool::new(true);
+
+// Like console::set_colors_enabled, set once from --no-clear
+pub fn set_clear_screen_enabled(enabled: bool) {
+    CLEAR_SCREEN.store(enabled, Ordering::Relaxed);
+}
+
+// Clears the terminal, or prints a separator line where clearing is turned off or fails
+pub fn clear_screen() {
+    if let Some(separator) =
+        screen_separator(CLEAR_SCREEN.load(Ordering::Relaxed), clearscreen::clear)
+    {
+        println!("{}", separator);
+    }
+}
+
+// None when the screen was cleared
+fn screen_separator<E>(enabled: bool, clear: impl FnOnce() -> Result<(), E>) -> Option<String> {
+    if enabled && clear().is_ok() {
+        None
+    } else {
+        Some(format!("\n{}\n", "─".repeat(40)))
+    }
+}
+
+// Asks again when a prompt fails, like on a terminal hiccup, and gives up after the attempts
+// with the last error. Errors that shouldn't be retried are returned right away.
+pub fn retry_input<T, E: Display>(
+    attempts: usize,
+    should_retry: impl Fn(&E) -> bool,
+    mut prompt: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut attempt = 1;
+    loop {
+        match prompt() {
+            Err(error) if attempt < attempts && should_retry(&error) => {
+                eprintln!("Couldn't read input ({}), trying again.", error);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+// Drawn on stderr while guesses are ranked, only when stderr is a terminal and there are enough
+// words for the ranking to take a while. The bar is cleared when it's dropped.
+pub struct ProgressBar {
+    label: &'static str,
+    enabled: bool,
+    drawn_percent: AtomicUsize,
+}
+
+impl ProgressBar {
+    pub fn new(label: &'static str, word_count: usize) -> Self {
+        Self {
+            label,
+            enabled: word_count >= 500 && Term::stderr().is_term(),
+            drawn_percent: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    // Only redrawn when the percentage changes, as it's called for every scored guess
+    pub fn update(&self, done: usize, total: usize) {
+        if !self.enabled {
+            return;
+        }
+        let percent = done * 100 / total.max(1);
+        if self.drawn_percent.swap(percent, Ordering::Relaxed) != percent {
+            eprint!("\r{} {}", self.label, progress_bar(done, total, 30));
+        }
+    }
+}
+
+impl Drop for ProgressBar {
+    fn drop(&mut self) {
+        if self.enabled {
+            let _ = Term::stderr().clear_line();
+        }
+    }
+}
+
+// The average and a row per number of guesses, with bars scaled to the most common one.
+// Failures are the X row.
+pub fn benchmark_histogram(report: &BenchmarkReport) -> String {
+    let mut histogram = format!(
+        "Average {:.2} guesses over {} games.",
+        report.average_guesses(),
+        report.games
+    );
+    let rows = (1..=6)
+        .map(|guesses| (guesses.to_string(), report.distribution[guesses - 1]))
+        .chain([("X".to_string(), report.failures)]);
+    let most = report
+        .distribution
+        .iter()
+        .chain([&report.failures])
+        .max()
+        .copied()
+        .unwrap_or_default()
+        .max(1);
+    for (label, count) in rows {
+        let _ = write!(
+            histogram,
+            "\n{} {:>6} {}",
+            label,
+            count,
+            "#".repeat(count * 40 / most)
+        );
+    }
+    histogram
+}
+
+// Like "[#####-----] 50%"
+pub fn progress_bar(done: usize, total: usize, width: usize) -> String {
+    let done = done.min(total);
+    let filled = done * width / total.max(1);
+    format!(
+        "[{}{}] {}%",
+        "#".repeat(filled),
+        "-".repeat(width - filled),
+        done * 100 / total.max(1)
+    )
+}
+
+// The line shown after a suggestion filter, only when the filter was used and the output isn't
+// quiet
+pub fn filter_message<'a>(result: &FilterResult, message: &'a str, quiet: bool) -> Option<&'a str> {
+    (result.filtered && !quiet).then_some(message)
+}
+
+pub fn exported_message(count: usize, path: &Path) -> String {
+    let words = if count == 1 { "word" } else { "words" };
+    format!("Wrote {} {} to {}.", count, words, path.display())
+}
+
+// Shown with --keep-going while the last word still matches every clue entered
+pub fn confirmed_message(possible_words: &[String]) -> Option<String> {
+    match possible_words {
+        [word] => Some(format!(
+            "1 word remaining: {} — confirmed consistent",
+            word.to_uppercase()
+        )),
+        _ => None,
+    }
+}
+
+// How many words the last guess removed, only shown in interactive games
+pub fn narrowed_message(before: usize, after: usize) -> String {
+    format!(
+        "Narrowed {} → {} (eliminated {}).",
+        before,
+        after,
+        before.saturating_sub(after)
+    )
+}
+
+// A rough idea of how close the game is to the answer, shown with the narrowed message
+pub const fn confidence_label(count: usize) -> &'static str {
+    match count {
+        0..=1 => "Very close (1 word)",
+        2..=5 => "Nearly there (2-5)",
+        6..=20 => "Narrowing (6-20)",
+        _ => "Wide open (20+)",
+    }
+}
+
+// Warns about words that can only be told apart one guess at a time
+pub fn cluster_warning(pattern: &str, words: &[String]) -> String {
+    format!(
+        "{} words only differ in one letter ({}): {}. They may take several guesses.",
+        words.len(),
+        pattern.to_uppercase(),
+        words.join(", ")
+    )
+}
+
+pub fn coverage_message(word: &str, percent: usize) -> String {
+    format!(
+        "{} — covers {}% of remaining letters.",
+        word.to_uppercase(),
+        percent
+    )
+}
+
+// The information regret of a guess, rounded like the entropy of ranked guesses
+pub fn regret_message(best_guess: &str, bits: f64) -> String {
+    if format!("{:.2}", bits) == "0.00" {
+        "This guess is as good as the best one.".to_string()
+    } else {
+        format!(
+            "This guess is {:.2} bits worse than optimal ({}).",
+            bits,
+            best_guess.to_uppercase()
+        )
+    }
+}
+
+// Feedback for a word Wordle wouldn't accept was probably typed for another word
+pub fn unknown_guess_message(guess: &str, dictionary: &[String]) -> Option<String> {
+    (!dictionary.iter().any(|word| word.to_lowercase() == guess)).then(|| {
+        format!(
+            "'{}' is not in the dictionary — feedback may be incorrect.",
+            guess
+        )
+    })
+}
+
+// The letters colored like Wordle's keyboard, in the keyboard layout of the language, or in
+// alphabet order for languages using the English layout with extra letters. Without colors
+// green letters are CAPITALS, yellow ones lower case and gray ones left out.
+pub fn keyboard(game: &Game) -> String {
+    let rows = match game.language {
+        GameLanguage::English => vec!["qwertyuiop", "asdfghjkl", "zxcvbnm"],
+        GameLanguage::Swedish => vec!["qwertyuiopå", "asdfghjklöä", "zxcvbnm"],
+        GameLanguage::German => vec!["qwertzuiopü", "asdfghjklöä", "yxcvbnmß"],
+        GameLanguage::Norwegian => vec!["qwertyuiopå", "asdfghjkløæ", "zxcvbnm"],
+        GameLanguage::Danish => vec!["qwertyuiopå", "asdfghjklæø", "zxcvbnm"],
+        _ => Vec::new(),
+    };
+    // Keyboard rows are indented a bit more for each row, like the keys
+    let stagger = usize::from(!rows.is_empty());
+    let rows: Vec<Vec<char>> = if rows.is_empty() {
+        game.language
+            .alphabet()
+            .chunks(10)
+            .map(<[char]>::to_vec)
+            .collect()
+    } else {
+        rows.iter().map(|row| row.chars().collect()).collect()
+    };
+    let colors = console::colors_enabled();
+    rows.iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let keys: Vec<String> = row
+                .iter()
+                .map(|&letter| match (game.letter_state(letter), colors) {
+                    (LetterState::Green, _) => {
+                        style(letter.to_uppercase()).green().bold().to_string()
+                    }
+                    (LetterState::Yellow, _) => style(letter).yellow().to_string(),
+                    (LetterState::Gray, true) => style(letter).dim().to_string(),
+                    (LetterState::Gray, false) => " ".to_string(),
+                    (LetterState::Unknown, _) => letter.to_string(),
+                })
+                .collect();
+            format!("{}{}", " ".repeat(index * stagger), keys.join(" "))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// The version and the bundled word lists, with a hash of each so installs can be compared
+pub fn version_info() -> String {
+    let mut info = format!(
+        "wordlehelper {}\n\nBundled word lists:\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    for list in EMBEDDED_LISTS {
+        let _ = writeln!(
+            info,
+            "  {:<15} {:?}, {} letters, {} words, hash {:016x}",
+            list.file,
+            list.language,
+            list.length as usize,
+            list.word_count(),
+            list.hash()
+        );
+    }
+    info
+}
+
+pub fn unknown_letters_message(letters: &[char]) -> String {
+    if letters.is_empty() {
+        return "Every letter is known.".to_string();
+    }
+    let letters: Vec<String> = letters
+        .iter()
+        .map(|letter| letter.to_uppercase().to_string())
+        .collect();
+    format!("Letters still unknown: {}", letters.join(" "))
+}
+
+// The playfield and wrong letters to type for a guess and its Wordle colors
+pub fn clue_input(guess: &str, feedback: &[Feedback]) -> (String, String) {
+    let mut playfield = String::new();
+    let mut wrong_letters = String::new();
+    for (letter, result) in guess.chars().zip(feedback) {
+        match result {
+            Feedback::Green => playfield.extend(letter.to_uppercase()),
+            Feedback::Yellow => playfield.extend(letter.to_lowercase()),
+            Feedback::Gray => {
+                playfield.push('-');
+                wrong_letters.extend(letter.to_lowercase());
+            }
+        }
+    }
+    (playfield, wrong_letters)
+}
+
+// A worked example of a guess, its Wordle colors and what to type for them
+pub fn tutorial(answer: &str, guess: &str, feedback: &[Feedback]) -> String {
+    let mut tiles = String::new();
+    let mut squares = String::new();
+    let mut letters = [Vec::new(), Vec::new(), Vec::new()];
+    for (letter, &result) in guess.to_uppercase().chars().zip(feedback) {
+        let tile = style(format!(" {} ", letter)).black().bold();
+        let (tile, square, index) = match result {
+            Feedback::Green => (tile.on_green(), '🟩', 0),
+            Feedback::Yellow => (tile.on_yellow(), '🟨', 1),
+            Feedback::Gray => (tile.on_white(), '⬛', 2),
+        };
+        let _ = write!(tiles, "{}", tile);
+        squares.push(square);
+        letters[index].push(letter.to_string());
+    }
+    let (playfield, wrong_letters) = clue_input(guess, feedback);
+
+    let mut text = format!(
+        "Say the answer is {} and you guess {}. Wordle shows\n\n  {}\n  {}\n\n",
+        answer.to_uppercase(),
+        guess.to_uppercase(),
+        tiles,
+        squares
+    );
+    let explanations = [
+        "Green {}: type in CAPITALS in its slot of the playfield",
+        "Yellow {}: type in lower case in its slot, it's remembered for that slot in later guesses",
+        "Gray {}: type with the characters not in word",
+    ];
+    for (letters, explanation) in letters.iter().zip(explanations) {
+        if !letters.is_empty() {
+            text.push_str(&explanation.replace("{}", &letters.join(", ")));
+            text.push('\n');
+        }
+    }
+    let _ = write!(
+        text,
+        "\nEnter current playfield: {}\nCharacters not in word?: {}\n",
+        playfield, wrong_letters
+    );
+    text
+}
+
+// A JSON object with a list of words for each key, keys are kept in the given order
+pub fn words_to_json(lists: &[(&str, &[String])]) -> String {
+    let mut json = String::from("{");
+    for (index, (key, words)) in lists.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        let _ = write!(json, "{}:[", json_string(key));
+        let words: Vec<String> = words.iter().map(|word| json_string(word)).collect();
+        json.push_str(&words.join(","));
+        json.push(']');
+    }
+    json.push('}');
+    json
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use wordlehelper::{solve, GameLanguage, GameLength};
+
+    use super::*;
+
+    #[test]
+    fn test_clue_input() {
+        let feedback = [
+            Feedback::Green,
+            Feedback::Gray,
+            Feedback::Yellow,
+            Feedback::Gray,
+            Feedback::Gray,
+        ];
+        assert_eq!(
+            clue_input("slate", &feedback),
+            ("S-a--".to_string(), "lte".to_string())
+        );
+
+        console::set_colors_enabled(false);
+        let text = tutorial("sandy", "slate", &feedback);
+        assert!(text.contains("Say the answer is SANDY and you guess SLATE."));
+        assert!(text.contains("🟩⬛🟨⬛⬛"));
+        assert!(text.contains("Gray L, T, E: type with the characters not in word"));
+        assert!(text.ends_with("playfield: S-a--\nCharacters not in word?: lte\n"));
+    }
+
+    #[test]
+    fn test_too_many_words_message() {
+        assert_eq!(
+            too_many_words_message(31, Some(30)).as_deref(),
+            Some("Too many words to print (31).")
+        );
+        assert_eq!(too_many_words_message(30, Some(30)), None);
+        assert_eq!(too_many_words_message(100, Some(100)), None);
+        assert!(too_many_words_message(101, Some(100)).is_some());
+        assert_eq!(too_many_words_message(1000, None), None);
+    }
+
+    #[test]
+    fn test_column_count() {
+        // 5 letter words take 9 chars with the gap, the last column needs only 5
+        assert_eq!(column_count(80, 5), 9);
+        assert_eq!(column_count(77, 5), 9);
+        assert_eq!(column_count(76, 5), 8);
+        assert_eq!(column_count(40, 6), 4);
+        assert_eq!(column_count(3, 5), 1);
+        assert_eq!(default_column_count(10), 4);
+    }
+
+    #[test]
+    fn test_colors_enabled() {
+        assert!(colors_enabled(false, None, true));
+        assert!(!colors_enabled(false, Some(OsStr::new("1")), true));
+        assert!(!colors_enabled(false, Some(OsStr::new("false")), true));
+        assert!(colors_enabled(false, Some(OsStr::new("")), true));
+        assert!(!colors_enabled(true, None, true));
+        assert!(!colors_enabled(false, None, false));
+    }
+
+    #[test]
+    fn test_page_ranges() {
+        assert_eq!(page_ranges(10, Some(4)), [0..4, 4..8, 8..10]);
+        assert_eq!(page_ranges(8, Some(4)), [0..4, 4..8]);
+        assert_eq!(page_ranges(3, Some(4)), vec![(0..3)]);
+        assert_eq!(page_ranges(10, None), vec![(0..10)]);
+        assert_eq!(page_ranges(2, Some(0)), [0..1, 1..2]);
+        assert!(page_ranges(0, Some(4)).is_empty());
+    }
+
+    #[test]
+    fn test_words_to_json() {
+        let possible_words = ["slate".to_string(), "söker".to_string()];
+        let best_guesses = ["say \"hi\"\\".to_string()];
+        let json = words_to_json(&[
+            ("possible_words", &possible_words),
+            ("without_duplicate_letters", &[]),
+            ("best_guesses", &best_guesses),
+        ]);
+        assert_eq!(
+            json,
+            r#"{"possible_words":["slate","söker"],"without_duplicate_letters":[],"best_guesses":["say \"hi\"\\"]}"#
+        );
+    }
+
+    #[test]
+    fn test_format_rows() {
+        console::set_colors_enabled(false);
+        let game = Game::new(
+            GameLanguage::Swedish,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let words = ["öl", "slate", "bödel", "ab", "sparkle"].map(String::from);
+        let rows = format_rows(&words, 3, &game);
+        assert_eq!(rows, ["öl         slate      bödel", "ab         sparkle"]);
+        // Every column starts at the same char offset, even after the multibyte ö
+        for row in &rows {
+            let chars: Vec<char> = row.chars().collect();
+            assert_eq!(chars[10], ' ');
+            assert_ne!(chars[11], ' ');
+        }
+    }
+
+    #[test]
+    fn test_highlight_letters() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['S', '-', '-', '-', '-'],
+            vec![],
+        );
+        game.excluded_positions[1].push('a');
+
+        console::set_colors_enabled(false);
+        assert_eq!(highlight_letters("shade", &game), "shade");
+        assert_eq!(format_rows(&["shade".to_string()], 3, &game), ["shade"]);
+    }
+
+    #[test]
+    fn test_format_scored_word() {
+        console::set_colors_enabled(false);
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        assert_eq!(
+            format_scored_word("slate", "4 common letters", &game),
+            "slate (4 common letters)"
+        );
+    }
+
+    #[test]
+    fn test_positional_table() {
+        let frequencies = vec![
+            HashMap::from([('s', 2), ('c', 3)]),
+            HashMap::from([('r', 5)]),
+        ];
+        assert_eq!(positional_table(&frequencies, 5), "1: C 3    S 2\n2: R 5\n");
+    }
+
+    #[test]
+    fn test_game_summary() {
+        let guesses = ["--a-e", "SlA-E", "slate"].map(String::from);
+        assert_eq!(
+            game_summary(&guesses, Some("slate")),
+            "Solved in 3 guesses: SLATE\n1. --a-e\n2. SlA-E\n3. slate"
+        );
+        assert_eq!(
+            game_summary(&guesses[..1], None),
+            "Gave up after 1 guess.\n1. --a-e"
+        );
+    }
+
+    #[test]
+    fn test_sort_words() {
+        let words = ["slate", "crane", "ärter", "åskor", "zebra", "acta"].map(String::from);
+        assert_eq!(
+            sort_words(&words, WordOrder::List, GameLanguage::Swedish),
+            words
+        );
+        assert_eq!(
+            sort_words(&words, WordOrder::Alphabetical, GameLanguage::Swedish),
+            ["acta", "crane", "slate", "zebra", "åskor", "ärter"]
+        );
+        assert_eq!(
+            sort_words(&words, WordOrder::Length, GameLanguage::Swedish),
+            ["acta", "crane", "slate", "zebra", "åskor", "ärter"]
+        );
+        let words = ["zebra", "ärger", "apfel", "öfter", "ofen"].map(String::from);
+        assert_eq!(
+            sort_words(&words, WordOrder::Alphabetical, GameLanguage::German),
+            ["apfel", "ärger", "ofen", "öfter", "zebra"]
+        );
+
+        let words = ["étage", "otage", "etage", "école"].map(String::from);
+        assert_eq!(
+            sort_words(&words, WordOrder::Alphabetical, GameLanguage::French),
+            ["école", "etage", "étage", "otage"]
+        );
+        let words = ["nuevo", "ñandú", "oveja", "nariz"].map(String::from);
+        assert_eq!(
+            sort_words(&words, WordOrder::Alphabetical, GameLanguage::Spanish),
+            ["nariz", "nuevo", "ñandú", "oveja"]
+        );
+        let words = ["slate", "cat", "crane", "dog"].map(String::from);
+        assert_eq!(
+            sort_words(&words, WordOrder::Length, GameLanguage::English),
+            ["cat", "dog", "crane", "slate"]
+        );
+        assert_eq!(WordOrder::Length.next(), WordOrder::List);
+    }
+
+    #[test]
+    fn test_screen_separator() {
+        let separator = screen_separator(false, || -> Result<(), ()> {
+            panic!("The screen shouldn't be cleared")
+        });
+        assert!(separator.unwrap().contains('─'));
+        assert!(screen_separator(true, || Err("Not a terminal")).is_some());
+        assert!(screen_separator(true, || Ok::<(), ()>(())).is_none());
+    }
+
+    #[test]
+    fn test_retry_input() {
+        let mut calls = 0;
+        let result = retry_input(
+            3,
+            |_| true,
+            || {
+                calls += 1;
+                if calls == 1 {
+                    Err("Terminal hiccup")
+                } else {
+                    Ok("-ATCH")
+                }
+            },
+        );
+        assert_eq!(result, Ok("-ATCH"));
+        assert_eq!(calls, 2);
+
+        calls = 0;
+        let result = retry_input(
+            3,
+            |_| true,
+            || -> Result<(), _> {
+                calls += 1;
+                Err("Gone")
+            },
+        );
+        assert_eq!(result, Err("Gone"));
+        assert_eq!(calls, 3);
+
+        calls = 0;
+        let result = retry_input(
+            3,
+            |_| false,
+            || -> Result<(), _> {
+                calls += 1;
+                Err("Interrupted")
+            },
+        );
+        assert_eq!(result, Err("Interrupted"));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_benchmark_histogram() {
+        let report = BenchmarkReport {
+            games: 4,
+            total_guesses: 14,
+            distribution: [0, 1, 2, 0, 0, 0],
+            failures: 1,
+        };
+        let histogram = benchmark_histogram(&report);
+        let lines: Vec<&str> = histogram.lines().collect();
+        assert_eq!(lines[0], "Average 3.50 guesses over 4 games.");
+        assert_eq!(lines[1], "1      0 ");
+        assert_eq!(lines[2], format!("2      1 {}", "#".repeat(20)));
+        assert_eq!(lines[3], format!("3      2 {}", "#".repeat(40)));
+        assert_eq!(lines[7], format!("X      1 {}", "#".repeat(20)));
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(0, 200, 10), "[----------] 0%");
+        assert_eq!(progress_bar(100, 200, 10), "[#####-----] 50%");
+        assert_eq!(progress_bar(200, 200, 10), "[##########] 100%");
+        assert_eq!(progress_bar(0, 0, 4), "[----] 0%");
+    }
+
+    #[test]
+    fn test_filter_message() {
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let words = ["slate", "sassy"].map(String::from);
+        let result = wordlehelper::filter::words_without_duplicate_letters(&words);
+        assert_eq!(result.words, ["slate"]);
+        assert_eq!(
+            filter_message(&result, "Filtering out", false),
+            Some("Filtering out")
+        );
+        assert_eq!(filter_message(&result, "Filtering out", true), None);
+
+        let words = ["jumpy".to_string()];
+        let result = wordlehelper::filter::words_without_uncommon_letters(&words, &game);
+        assert!(!result.filtered);
+        assert_eq!(filter_message(&result, "Filtering out", false), None);
+    }
+
+    #[test]
+    fn test_confirmed_message() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            "SL---".chars().collect(),
+            vec![],
+        );
+        let words = solve(&game, &["slate", "crane"].map(String::from));
+        assert_eq!(
+            confirmed_message(&words).as_deref(),
+            Some("1 word remaining: SLATE — confirmed consistent")
+        );
+
+        // The last word is checked again against the next clues
+        game.playfield = "--A-E".chars().collect();
+        let words = solve(&game, &words);
+        assert_eq!(
+            confirmed_message(&words).as_deref(),
+            Some("1 word remaining: SLATE — confirmed consistent")
+        );
+        game.wrong_letters.push('t');
+        assert_eq!(confirmed_message(&solve(&game, &words)), None);
+        assert_eq!(
+            confirmed_message(&["slate", "crane"].map(String::from)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unknown_guess_message() {
+        let dictionary = ["slate", "Crane"].map(String::from);
+        assert_eq!(
+            unknown_guess_message("slorp", &dictionary).as_deref(),
+            Some("'slorp' is not in the dictionary — feedback may be incorrect.")
+        );
+        assert_eq!(unknown_guess_message("slate", &dictionary), None);
+        assert_eq!(unknown_guess_message("crane", &dictionary), None);
+    }
+
+    #[test]
+    fn test_regret_message() {
+        assert_eq!(
+            regret_message("slate", 0.8),
+            "This guess is 0.80 bits worse than optimal (SLATE)."
+        );
+        assert_eq!(
+            regret_message("slate", 0.001),
+            "This guess is as good as the best one."
+        );
+    }
+
+    #[test]
+    fn test_exported_message() {
+        assert_eq!(
+            exported_message(47, Path::new("words.txt")),
+            "Wrote 47 words to words.txt."
+        );
+        assert_eq!(
+            exported_message(1, Path::new("words.txt")),
+            "Wrote 1 word to words.txt."
+        );
+    }
+
+    #[test]
+    fn test_narrowed_message() {
+        assert_eq!(
+            narrowed_message(2315, 47),
+            "Narrowed 2315 → 47 (eliminated 2268)."
+        );
+        assert_eq!(narrowed_message(3, 3), "Narrowed 3 → 3 (eliminated 0).");
+    }
+
+    #[test]
+    fn test_cluster_warning() {
+        let words = ["batch", "catch", "latch"].map(String::from);
+        assert_eq!(
+            cluster_warning("_atch", &words),
+            "3 words only differ in one letter (_ATCH): batch, catch, latch. They may take \
+             several guesses."
+        );
+    }
+
+    #[test]
+    fn test_coverage_message() {
+        assert_eq!(
+            coverage_message("slate", 62),
+            "SLATE — covers 62% of remaining letters."
+        );
+    }
+
+    #[test]
+    fn test_confidence_label() {
+        for (count, label) in [
+            (1, "Very close (1 word)"),
+            (2, "Nearly there (2-5)"),
+            (5, "Nearly there (2-5)"),
+            (6, "Narrowing (6-20)"),
+            (20, "Narrowing (6-20)"),
+            (21, "Wide open (20+)"),
+            (2315, "Wide open (20+)"),
+        ] {
+            assert_eq!(confidence_label(count), label, "{}", count);
+        }
+    }
+
+    #[test]
+    fn test_keyboard() {
+        console::set_colors_enabled(false);
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['S', '-', 'a', '-', '-'],
+            vec!['q', 'z'],
+        );
+        game.update_letter_counts();
+        assert_eq!(
+            keyboard(&game),
+            "  w e r t y u i o p\n a S d f g h j k l\n    x c v b n m"
+        );
+        game.language = GameLanguage::Spanish;
+        assert_eq!(keyboard(&game).lines().last(), Some("u v w x y   ñ"));
+    }
+
+    #[test]
+    fn test_version_info() {
+        let info = version_info();
+        assert!(info.starts_with(&format!("wordlehelper {}", env!("CARGO_PKG_VERSION"))));
+        let hash = format!(
+            "{:016x}",
+            wordlehelper::wordlist::content_hash(include_str!("../english5.txt"))
+        );
+        let english = info
+            .lines()
+            .find(|line| line.contains("english5.txt"))
+            .unwrap();
+        assert!(english.ends_with(&format!("English, 5 letters, 2309 words, hash {}", hash)));
+        assert_eq!(info.lines().count(), 3 + EMBEDDED_LISTS.len());
+    }
+
+    #[test]
+    fn test_unknown_letters_message() {
+        assert_eq!(
+            unknown_letters_message(&['b', 'c', 'ö']),
+            "Letters still unknown: B C Ö"
+        );
+        assert_eq!(unknown_letters_message(&[]), "Every letter is known.");
+    }
+}
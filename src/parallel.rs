@@ -0,0 +1,55 @@
+use std::{num::NonZeroUsize, thread};
+
+// Map the items on up to one thread per core, keeping their order. Every thread gets at least
+// min_chunk_len items, so cheap work on short lists stays on the calling thread.
+pub fn map<T, U, F>(items: &[T], min_chunk_len: usize, f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync,
+{
+    let threads = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(items.len() / min_chunk_len.max(1));
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| chunk.iter().map(&f).collect::<Vec<U>>()))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("Worker thread panicked"))
+            .collect()
+    })
+}
+
+pub fn filter<T, F>(items: &[T], min_chunk_len: usize, predicate: F) -> Vec<T>
+where
+    T: Clone + Send + Sync,
+    F: Fn(&T) -> bool + Sync,
+{
+    map(items, min_chunk_len, |item| {
+        predicate(item).then(|| item.clone())
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let numbers: Vec<usize> = (0..10_000).collect();
+        let returned = map(&numbers, 100, |n| n * 2);
+        assert_eq!(returned, numbers.iter().map(|n| n * 2).collect::<Vec<_>>());
+        assert_eq!(filter(&numbers, 100, |n| n % 3 == 0).len(), 3334);
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::{bail, Result};
+
+pub const HELP: &str = "\
+Commands:
+  play <PLAYFIELD>    CAPITAL letters in correct slot, lower case in the wrong slot, _ or - if empty
+  gray <LETTERS>      Letters not in the word, gray -<LETTERS> removes them
+  ban <WORD>          Don't suggest the word
+  contains <LETTERS>  Show the possible words with all the letters, anywhere in the word
+  regret <WORD>       How much less the word tells than the best guess
+  undo                Undo the last play or gray
+  words               Show all possible words
+  best                Show the best guesses
+  help                Show this help
+  quit                End the game";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Play(String),
+    Gray(String),
+    Ban(String),
+    Contains(String),
+    Regret(String),
+    Undo,
+    Words,
+    Best,
+    Help,
+    Quit,
+}
+
+pub fn parse_command(input: &str) -> Result<Command> {
+    let mut parts = input.split_whitespace();
+    let Some(verb) = parts.next() else {
+        bail!("Type a command, help shows them all");
+    };
+    let argument = parts.next();
+    if parts.next().is_some() {
+        bail!("Too many arguments to {}", verb);
+    }
+    let lowercase = verb.to_lowercase();
+    let command = match (lowercase.as_str(), argument) {
+        ("play", Some(playfield)) => Command::Play(playfield.replace('_', "-")),
+        ("gray" | "grey", Some(letters)) => Command::Gray(letters.to_lowercase()),
+        ("ban", Some(word)) => Command::Ban(word.to_lowercase()),
+        ("contains", Some(letters)) => Command::Contains(letters.to_lowercase()),
+        ("regret", Some(word)) => Command::Regret(word.to_lowercase()),
+        ("play" | "gray" | "grey" | "ban" | "contains" | "regret", None) => {
+            bail!("{} needs an argument", verb)
+        }
+        ("undo", None) => Command::Undo,
+        ("words", None) => Command::Words,
+        ("best", None) => Command::Best,
+        ("help", None) => Command::Help,
+        ("quit" | "exit", None) => Command::Quit,
+        (name, Some(_)) if ["undo", "words", "best", "help", "quit", "exit"].contains(&name) => {
+            bail!("{} doesn't take an argument", verb)
+        }
+        _ => bail!("Unknown command {}, help shows them all", verb),
+    };
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse_command("play s_a_E").unwrap(),
+            Command::Play("s-a-E".to_string())
+        );
+        assert_eq!(
+            parse_command("  gray RTN ").unwrap(),
+            Command::Gray("rtn".to_string())
+        );
+        assert_eq!(
+            parse_command("ban Slate").unwrap(),
+            Command::Ban("slate".to_string())
+        );
+        assert_eq!(
+            parse_command("contains AE").unwrap(),
+            Command::Contains("ae".to_string())
+        );
+        assert_eq!(
+            parse_command("regret Crane").unwrap(),
+            Command::Regret("crane".to_string())
+        );
+        assert_eq!(parse_command("undo").unwrap(), Command::Undo);
+        assert_eq!(parse_command("words").unwrap(), Command::Words);
+        assert_eq!(parse_command("BEST").unwrap(), Command::Best);
+        assert_eq!(parse_command("help").unwrap(), Command::Help);
+        assert_eq!(parse_command("quit").unwrap(), Command::Quit);
+    }
+
+    #[test]
+    fn test_parse_command_errors() {
+        assert!(parse_command("").is_err());
+        assert!(parse_command("play").is_err());
+        assert!(parse_command("play s_a_e extra").is_err());
+        assert!(parse_command("undo now").is_err());
+        assert_eq!(
+            parse_command("UNDO now").unwrap_err().to_string(),
+            "UNDO doesn't take an argument"
+        );
+        assert!(parse_command("contains").is_err());
+        assert!(parse_command("guess slate").is_err());
+    }
+}
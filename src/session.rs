@@ -0,0 +1,156 @@
+use std::{fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{Game, GameLanguage, GameLength, LanguageProfile};
+
+// A saved session is the game state as "key value" lines, followed by a "words" line and the
+// remaining possible words, one per line.
+pub fn save_session(path: &Path, game: &Game, possible_words: &[String]) -> Result<()> {
+    let excluded_positions: Vec<String> = game
+        .excluded_positions
+        .iter()
+        .map(|letters| letters.iter().collect())
+        .collect();
+    let mut letter_counts: Vec<String> = game
+        .letter_counts
+        .iter()
+        .map(|(letter, (min, max))| format!("{}:{}:{}", letter, min, max))
+        .collect();
+    letter_counts.sort();
+
+    let mut banned_words: Vec<&str> = game.banned_words.iter().map(String::as_str).collect();
+    banned_words.sort_unstable();
+
+    let mut session = format!(
+        "language {:?}\nlength {}\nplayfield {}\nwrong {}\nexcluded {}\ncounts {}\nhard_mode {}\n\
+         banned {}\nfold_accents {}\ncommon {}\nuncommon {}\nwords\n",
+        game.language,
+        game.length as usize,
+        game.playfield.iter().collect::<String>(),
+        game.wrong_letters.iter().collect::<String>(),
+        excluded_positions.join(","),
+        letter_counts.join(" "),
+        game.hard_mode,
+        banned_words.join(" "),
+        game.fold_accents,
+        game.profile.common.iter().collect::<String>(),
+        game.profile.uncommon.iter().collect::<String>(),
+    );
+    for word in possible_words {
+        session.push_str(word);
+        session.push('\n');
+    }
+    fs::write(path, session)
+        .with_context(|| format!("Failed to save session to {}", path.display()))
+}
+
+pub fn load_session(path: &Path) -> Result<(Game, Vec<String>)> {
+    let session = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read session from {}", path.display()))?;
+    let mut lines = session.lines();
+    let mut value = |key: &str| -> Result<&str> {
+        let line = lines.next().unwrap_or_default();
+        line.strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(' '))
+            .with_context(|| format!("Expected {} in session, found {:?}", key, line))
+    };
+
+    let language: GameLanguage = value("language")?
+        .parse()
+        .context("Unknown language in session")?;
+    let length = GameLength::try_from(
+        value("length")?
+            .parse::<usize>()
+            .context("Session length is not a number")?,
+    )?;
+    let playfield: Vec<char> = value("playfield")?.chars().collect();
+    let wrong_letters = value("wrong")?.chars().collect();
+    let excluded_positions: Vec<Vec<char>> = value("excluded")?
+        .split(',')
+        .map(|letters| letters.chars().collect())
+        .collect();
+    if playfield.len() != length as usize || excluded_positions.len() != length as usize {
+        bail!(
+            "Session playfield doesn't match the length {}",
+            length as usize
+        );
+    }
+    let letter_counts = value("counts")?
+        .split_whitespace()
+        .map(parse_letter_count)
+        .collect::<Result<_>>()?;
+    let hard_mode = value("hard_mode")? == "true";
+    let banned_words = value("banned")?
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    let fold_accents = value("fold_accents")? == "true";
+    // The profile may come from a custom word list, which isn't read again on resume
+    let profile = LanguageProfile {
+        common: value("common")?.chars().collect(),
+        uncommon: value("uncommon")?.chars().collect(),
+    };
+    if lines.next() != Some("words") {
+        bail!("Expected words in session");
+    }
+    let possible_words = lines.map(String::from).collect();
+
+    let mut game = Game::new(language, length, playfield, wrong_letters);
+    game.excluded_positions = excluded_positions;
+    game.letter_counts = letter_counts;
+    game.hard_mode = hard_mode;
+    game.banned_words = banned_words;
+    game.fold_accents = fold_accents;
+    game.profile = profile;
+    Ok((game, possible_words))
+}
+
+// "r:1:2" is a letter that occurs at least once and at most twice
+fn parse_letter_count(count: &str) -> Result<(char, (usize, usize))> {
+    let parse = || -> Option<(char, (usize, usize))> {
+        let mut parts = count.split(':');
+        let letter = parts.next()?.chars().next()?;
+        let min = parts.next()?.parse().ok()?;
+        let max = parts.next()?.parse().ok()?;
+        Some((letter, (min, max)))
+    };
+    parse().with_context(|| format!("Invalid letter count {} in session", count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_session() {
+        let mut game = Game::new(
+            GameLanguage::Swedish,
+            GameLength::Five,
+            vec!['-', 'Ö', '-', '-', '-'],
+            vec!['k', 'r'],
+        );
+        game.excluded_positions[0].push('a');
+        game.excluded_positions[3].push('l');
+        game.update_letter_counts();
+        game.hard_mode = true;
+        game.fold_accents = true;
+        game.banned_words.insert("bölja".to_string());
+        game.banned_words.insert("kalas".to_string());
+        game.profile = LanguageProfile {
+            common: vec!['ö', 'l', 'j'],
+            uncommon: vec![],
+        };
+        let words = ["följa", "bölja"].map(String::from);
+
+        let path = std::env::temp_dir().join("wordlehelper_test_session.txt");
+        save_session(&path, &game, &words).unwrap();
+        let (loaded_game, loaded_words) = load_session(&path).unwrap();
+        assert_eq!(loaded_game, game);
+        assert_eq!(loaded_words, words);
+
+        fs::write(&path, "language Klingon\n").unwrap();
+        assert!(load_session(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{bail, Result};
+
+use crate::{feedback::compute_feedback, filter, parallel, solve, Game};
+
+// How the solver did against every word of a list. Games that take more than six guesses
+// count as failures.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BenchmarkReport {
+    pub games: usize,
+    pub total_guesses: usize,
+    // Games solved in 1 to 6 guesses
+    pub distribution: [usize; 6],
+    pub failures: usize,
+}
+
+impl BenchmarkReport {
+    pub const fn record(&mut self, guesses: usize, solved: bool) {
+        self.games += 1;
+        self.total_guesses += guesses;
+        match guesses {
+            1..=6 if solved => self.distribution[guesses - 1] += 1,
+            _ => self.failures += 1,
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average_guesses(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.total_guesses as f64 / self.games as f64
+    }
+}
+
+// Simulate a game against each of the words
+pub fn benchmark(game: &Game, words: &[String]) -> Result<BenchmarkReport> {
+    benchmark_with_progress(game, words, |_, _| {})
+}
+
+// Progress is called with the number of games played so far and the number of words
+pub fn benchmark_with_progress<P>(
+    game: &Game,
+    words: &[String],
+    progress: P,
+) -> Result<BenchmarkReport>
+where
+    P: Fn(usize, usize) + Sync,
+{
+    let played = AtomicUsize::new(0);
+    // Each game ranks guesses for a few words, so every game is worth a thread
+    let games = parallel::map(words, 1, |secret| {
+        let result = simulate(secret, game, words);
+        progress(played.fetch_add(1, Ordering::Relaxed) + 1, words.len());
+        result.map(|guesses| {
+            let solved = guesses.last().is_some_and(|guess| guess == secret);
+            (guesses.len(), solved)
+        })
+    });
+    let mut report = BenchmarkReport::default();
+    for result in games {
+        let (guesses, solved) = result?;
+        report.record(guesses, solved);
+    }
+    Ok(report)
+}
+
+// The answer of a daily puzzle from a list of answers in the order they were used. Puzzles are
+// numbered from 0, like Wordle's first puzzle.
+pub fn puzzle_answer(answers: &[String], puzzle: usize) -> Result<&str> {
+    match answers.get(puzzle) {
+        Some(answer) => Ok(answer),
+        None => bail!(
+            "No puzzle {}, the answer list only has puzzles 0 to {}",
+            puzzle,
+            answers.len().saturating_sub(1)
+        ),
+    }
+}
+
+// Play against a secret word, starting with the best opening word and then always guessing
+// the possible word with the most expected information. Returns the guesses in order, the
+// last one is the secret word if it was found.
+pub fn simulate(secret: &str, game: &Game, words: &[String]) -> Result<Vec<String>> {
+    let secret = secret.to_lowercase();
+    if secret.chars().count() != game.length as usize {
+        bail!("The secret word must have {} letters", game.length as usize);
+    }
+    let mut game = game.clone();
+    let mut possible_words = words.to_vec();
+    let mut guesses = Vec::new();
+    let mut guess = filter::best_opening_word(&possible_words, &game);
+    while let Some(current_guess) = guess {
+        guesses.push(current_guess.clone());
+        if current_guess.to_lowercase() == secret {
+            break;
+        }
+        let feedback = compute_feedback(&current_guess.to_lowercase(), &secret);
+        game.apply_feedback(&current_guess, &feedback)?;
+        possible_words = solve(&game, &possible_words);
+        // A wrong guess never matches its own feedback, removing it anyway makes sure this ends
+        possible_words.retain(|word| *word != current_guess);
+        guess = filter::rank_by_entropy(&possible_words)
+            .into_iter()
+            .next()
+            .map(|(word, _)| word);
+    }
+    Ok(guesses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wordlist, GameLanguage, GameLength};
+
+    #[test]
+    fn test_simulate() {
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let words = wordlist::read_file(&game, None).unwrap();
+        for secret in ["cigar", "humph", "knoll"] {
+            let guesses = simulate(secret, &game, &words).unwrap();
+            assert_eq!(guesses.last().map(String::as_str), Some(secret));
+            assert!(guesses.len() <= 6, "{} took {:?}", secret, guesses);
+        }
+        assert!(simulate("cat", &game, &words).is_err());
+    }
+
+    #[test]
+    fn test_benchmark_report() {
+        let mut report = BenchmarkReport::default();
+        for (guesses, solved) in [(1, true), (3, true), (3, true), (7, true), (4, false)] {
+            report.record(guesses, solved);
+        }
+        assert_eq!(report.games, 5);
+        assert_eq!(report.distribution, [1, 0, 2, 0, 0, 0]);
+        assert_eq!(report.failures, 2);
+        assert!((report.average_guesses() - 3.6).abs() < 1e-9);
+        assert!(BenchmarkReport::default().average_guesses().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_puzzle_answer() {
+        let answers = ["cigar", "rebut", "sissy", "humph"].map(String::from);
+        assert_eq!(puzzle_answer(&answers, 0).unwrap(), "cigar");
+        assert_eq!(puzzle_answer(&answers, 2).unwrap(), "sissy");
+        let error = puzzle_answer(&answers, 4).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No puzzle 4, the answer list only has puzzles 0 to 3"
+        );
+        assert!(puzzle_answer(&[], 0).is_err());
+    }
+
+    #[test]
+    fn test_benchmark() {
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let words = ["crane", "crate", "trace", "slate", "plumb"].map(String::from);
+        let report = benchmark(&game, &words).unwrap();
+        assert_eq!(report.games, words.len());
+        assert_eq!(report.failures, 0);
+        // The opening word is only one guess when it is the secret
+        assert_eq!(report.distribution[0], 1);
+        assert_eq!(report.distribution.iter().sum::<usize>(), words.len());
+        let total: usize = words
+            .iter()
+            .map(|secret| simulate(secret, &game, &words).unwrap().len())
+            .sum();
+        assert_eq!(report.total_guesses, total);
+    }
+}
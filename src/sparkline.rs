@@ -0,0 +1,38 @@
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// Each count followed by a block, like "2315 █ 140 ▅ 12 ▃ 1 ▁". The blocks are scaled to the
+// largest count on a logarithmic scale, so the small counts at the end of a game still differ.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn render(counts: &[usize]) -> String {
+    let max = counts.iter().copied().max().unwrap_or_default();
+    counts
+        .iter()
+        .map(|&count| {
+            let level = if count <= 1 || max <= 1 {
+                0
+            } else {
+                let scaled = (count as f64).log(max as f64);
+                (scaled * (BLOCKS.len() - 1) as f64).round() as usize
+            };
+            format!("{} {}", count, BLOCKS[level])
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(render(&[2315, 140, 12, 1]), "2315 █ 140 ▅ 12 ▃ 1 ▁");
+        assert_eq!(render(&[100, 10, 0]), "100 █ 10 ▅ 0 ▁");
+        assert_eq!(render(&[1]), "1 ▁");
+        assert_eq!(render(&[]), "");
+    }
+}
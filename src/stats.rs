@@ -0,0 +1,90 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{GameLanguage, GameLength};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameResult {
+    pub language: GameLanguage,
+    pub length: GameLength,
+    pub guesses: usize,
+    // False if the game was given up
+    pub solved: bool,
+}
+
+impl GameResult {
+    // One JSON object, time is in seconds since the Unix epoch
+    pub fn to_json_line(&self, time: u64) -> String {
+        format!(
+            "{{\"time\":{},\"language\":\"{}\",\"length\":{},\"guesses\":{},\"solved\":{}}}\n",
+            time,
+            format!("{:?}", self.language).to_lowercase(),
+            self.length as usize,
+            self.guesses,
+            self.solved
+        )
+    }
+}
+
+// Append the result as a JSON line. The file is opened in append mode and each line is written
+// at once, so games ending at the same time in other processes don't mix their lines.
+pub fn record_game_result(path: &Path, result: &GameResult) -> Result<()> {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open stats file {}", path.display()))?;
+    file.write_all(result.to_json_line(time).as_bytes())
+        .with_context(|| format!("Failed to write to stats file {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{simulate::simulate, wordlist, Game};
+
+    #[test]
+    fn test_record_game_result() {
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let words = wordlist::read_file(&game, None).unwrap();
+        let guesses = simulate("cigar", &game, &words).unwrap();
+        let result = GameResult {
+            language: game.language,
+            length: game.length,
+            guesses: guesses.len(),
+            solved: guesses.last().is_some_and(|guess| guess == "cigar"),
+        };
+
+        let path = std::env::temp_dir().join("wordlehelper_test_stats.jsonl");
+        let _ = std::fs::remove_file(&path);
+        record_game_result(&path, &result).unwrap();
+        record_game_result(&path, &result).unwrap();
+        let stats = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let lines: Vec<&str> = stats.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let expected_end = format!(
+            ",\"language\":\"english\",\"length\":5,\"guesses\":{},\"solved\":true}}",
+            guesses.len()
+        );
+        for line in lines {
+            assert!(line.starts_with("{\"time\":"), "{}", line);
+            assert!(line.ends_with(&expected_end), "{}", line);
+        }
+    }
+}
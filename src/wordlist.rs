@@ -0,0 +1,773 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    io::prelude::*,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{Game, GameLanguage, GameLength};
+
+const ENGLISH_FIVE: &str = include_str!("../english5.txt");
+const SWEDISH_FIVE: &str = include_str!("../svenska5.txt");
+const SWEDISH_SIX: &str = include_str!("../svenska6.txt");
+const GERMAN_FIVE: &str = include_str!("../deutsch5.txt");
+const SPANISH_FIVE: &str = include_str!("../espanol5.txt");
+const FRENCH_FIVE: &str = include_str!("../francais5.txt");
+const ITALIAN_FIVE: &str = include_str!("../italiano5.txt");
+const PORTUGUESE_FIVE: &str = include_str!("../portugues5.txt");
+const NORWEGIAN_FIVE: &str = include_str!("../norsk5.txt");
+const DANISH_FIVE: &str = include_str!("../dansk5.txt");
+
+// A word list bundled in the binary, with the file it was built from
+#[derive(Debug)]
+pub struct EmbeddedList {
+    pub language: GameLanguage,
+    pub length: GameLength,
+    pub file: &'static str,
+    pub words: &'static str,
+}
+
+impl EmbeddedList {
+    pub fn word_count(&self) -> usize {
+        self.words
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .count()
+    }
+
+    // Changes whenever the list does, to tell which version of a list a binary has
+    pub fn hash(&self) -> u64 {
+        content_hash(self.words)
+    }
+}
+
+pub const EMBEDDED_LISTS: &[EmbeddedList] = &[
+    embedded(
+        GameLanguage::English,
+        GameLength::Five,
+        "english5.txt",
+        ENGLISH_FIVE,
+    ),
+    embedded(
+        GameLanguage::Swedish,
+        GameLength::Five,
+        "svenska5.txt",
+        SWEDISH_FIVE,
+    ),
+    embedded(
+        GameLanguage::Swedish,
+        GameLength::Six,
+        "svenska6.txt",
+        SWEDISH_SIX,
+    ),
+    embedded(
+        GameLanguage::German,
+        GameLength::Five,
+        "deutsch5.txt",
+        GERMAN_FIVE,
+    ),
+    embedded(
+        GameLanguage::Spanish,
+        GameLength::Five,
+        "espanol5.txt",
+        SPANISH_FIVE,
+    ),
+    embedded(
+        GameLanguage::French,
+        GameLength::Five,
+        "francais5.txt",
+        FRENCH_FIVE,
+    ),
+    embedded(
+        GameLanguage::Italian,
+        GameLength::Five,
+        "italiano5.txt",
+        ITALIAN_FIVE,
+    ),
+    embedded(
+        GameLanguage::Portuguese,
+        GameLength::Five,
+        "portugues5.txt",
+        PORTUGUESE_FIVE,
+    ),
+    embedded(
+        GameLanguage::Norwegian,
+        GameLength::Five,
+        "norsk5.txt",
+        NORWEGIAN_FIVE,
+    ),
+    embedded(
+        GameLanguage::Danish,
+        GameLength::Five,
+        "dansk5.txt",
+        DANISH_FIVE,
+    ),
+];
+
+const fn embedded(
+    language: GameLanguage,
+    length: GameLength,
+    file: &'static str,
+    words: &'static str,
+) -> EmbeddedList {
+    EmbeddedList {
+        language,
+        length,
+        file,
+        words,
+    }
+}
+
+pub fn embedded_words(language: GameLanguage, length: GameLength) -> Option<&'static str> {
+    EMBEDDED_LISTS
+        .iter()
+        .find(|list| list.language == language && list.length == length)
+        .map(|list| list.words)
+}
+
+// 64 bit FNV-1a, short enough to print and doesn't depend on the Rust version
+pub fn content_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// What to do with words that have other characters than the letters of the language, like
+// "can't"
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum InvalidWords {
+    // Leave them out and report how many there were
+    #[default]
+    Skip,
+    Reject,
+}
+
+// Use the word list bundled in the binary unless a file is explicitly given. The skipped words
+// are left out without a report, read_file_with returns them.
+pub fn read_file(game: &Game, path: Option<&Path>) -> Result<Vec<String>> {
+    Ok(read_file_with(game, path, InvalidWords::default())?.0)
+}
+
+// Nothing is skipped from the bundled lists
+pub fn read_file_with(
+    game: &Game,
+    path: Option<&Path>,
+    invalid_words: InvalidWords,
+) -> Result<(Vec<String>, SkippedWords)> {
+    if let Some(path) = path {
+        return read_file_from_path(path, game, invalid_words);
+    }
+    let Some(words) = embedded_words(game.language, game.length) else {
+        bail!(
+            "No bundled {:?} word list with {} letters, use --wordlist",
+            game.language,
+            game.length as usize
+        );
+    };
+    let words = normalize_words(words.lines(), game.language, game.length as usize).0;
+    Ok((words, SkippedWords::default()))
+}
+
+// Word lists already read in this run, so following games don't read and normalize them
+// again. A custom word list is the same for the whole run, so language and length are enough to
+// tell the lists apart.
+#[derive(Debug, Default)]
+pub struct WordListCache {
+    lists: HashMap<(GameLanguage, GameLength), WordBuffer>,
+    invalid_words: InvalidWords,
+    // Merged into every list that is read, like --extra-wordlist
+    extra_wordlist: Option<PathBuf>,
+}
+
+impl WordListCache {
+    pub fn new(invalid_words: InvalidWords, extra_wordlist: Option<PathBuf>) -> Self {
+        Self {
+            invalid_words,
+            extra_wordlist,
+            ..Self::default()
+        }
+    }
+
+    pub fn read(
+        &mut self,
+        game: &Game,
+        path: Option<&Path>,
+    ) -> Result<(Vec<String>, Vec<SkippedWords>)> {
+        let (words, skipped) = self.read_buffer(game, path)?;
+        Ok((words.to_strings(), skipped))
+    }
+
+    // The cached list itself, solving on its words() only copies out the words that are left.
+    // The skipped words of the list and the extra list are only returned when they are read.
+    pub fn read_buffer(
+        &mut self,
+        game: &Game,
+        path: Option<&Path>,
+    ) -> Result<(&WordBuffer, Vec<SkippedWords>)> {
+        let key = (game.language, game.length);
+        let mut skipped = Vec::new();
+        if !self.lists.contains_key(&key) {
+            let (mut words, list_skipped) = read_file_with(game, path, self.invalid_words)?;
+            skipped.push(list_skipped);
+            if let Some(extra_path) = &self.extra_wordlist {
+                let (extra, extra_skipped) =
+                    read_file_from_path(extra_path, game, self.invalid_words)?;
+                words = merge_words(&words, &extra);
+                skipped.push(extra_skipped);
+            }
+            self.lists.insert(key, WordBuffer::new(&words));
+        }
+        Ok((&self.lists[&key], skipped))
+    }
+}
+
+// The words of a list in a single string, so a long list isn't one allocation per word.
+// solve works on the slices from words() the same way as on a Vec<String>.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordBuffer {
+    text: String,
+    ranges: Vec<Range<usize>>,
+}
+
+impl WordBuffer {
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut buffer = Self::default();
+        for word in words {
+            let start = buffer.text.len();
+            buffer.text.push_str(word.as_ref());
+            buffer.ranges.push(start..buffer.text.len());
+        }
+        buffer
+    }
+
+    pub const fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.ranges
+            .get(index)
+            .map(|range| &self.text[range.clone()])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.ranges.iter().map(|range| &self.text[range.clone()])
+    }
+
+    pub fn words(&self) -> Vec<&str> {
+        self.iter().collect()
+    }
+
+    pub fn to_strings(&self) -> Vec<String> {
+        self.iter().map(String::from).collect()
+    }
+}
+
+// The words of both lists once, the extra words that aren't in the first list after it
+pub fn merge_words(words: &[String], extra: &[String]) -> Vec<String> {
+    let listed: HashSet<&str> = words.iter().map(String::as_str).collect();
+    let mut merged = words.to_vec();
+    merged.extend(
+        extra
+            .iter()
+            .filter(|word| !listed.contains(word.as_str()))
+            .cloned(),
+    );
+    merged
+}
+
+// The length of the first word in a word list
+pub fn infer_length(path: &Path) -> Result<GameLength> {
+    let file = fs::File::open(path).with_context(|| open_error(path))?;
+    for line in io::BufReader::new(file).lines() {
+        let line = line?;
+        if let Some(word) = line.split_whitespace().next() {
+            return GameLength::try_from(word.chars().count());
+        }
+    }
+    bail!("{} is empty", path.display())
+}
+
+// The words left out of a word list file, with the line they were on
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SkippedWords {
+    pub path: PathBuf,
+    pub length: usize,
+    pub wrong_length: NumberedWords,
+    pub invalid: NumberedWords,
+}
+
+impl SkippedWords {
+    // The warnings to show for the skipped words, none if nothing was skipped
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !self.wrong_length.is_empty() {
+            warnings.push(format!(
+                "Skipped {} words in {} without {} letters, first ones are:\n{}",
+                self.wrong_length.len(),
+                self.path.display(),
+                self.length,
+                first_lines(&self.wrong_length)
+            ));
+        }
+        if !self.invalid.is_empty() {
+            warnings.push(format!(
+                "Skipped {} words in {} with other characters than letters, first ones are:\n{}",
+                self.invalid.len(),
+                self.path.display(),
+                first_lines(&self.invalid)
+            ));
+        }
+        warnings
+    }
+}
+
+// Read a newline separated word list with words of the game's length. A word can be followed
+// by its frequency, which is skipped here and read by read_frequencies. Words without the
+// length are skipped and returned, like words with other characters than letters unless they
+// are rejected.
+pub fn read_file_from_path(
+    path: &Path,
+    game: &Game,
+    invalid_words: InvalidWords,
+) -> Result<(Vec<String>, SkippedWords)> {
+    let length = game.length as usize;
+    let contents = fs::read_to_string(path).with_context(|| open_error(path))?;
+    let (possible_words, skipped, invalid) =
+        normalize_words(contents.lines(), game.language, length);
+    if !invalid.is_empty() && invalid_words == InvalidWords::Reject {
+        bail!(
+            "{} words in {} have other characters than letters, first ones are:\n{}",
+            invalid.len(),
+            path.display(),
+            first_lines(&invalid)
+        );
+    }
+    if possible_words.is_empty() {
+        bail!("No words with {} letters in {}", length, path.display());
+    }
+    let skipped = SkippedWords {
+        path: path.to_path_buf(),
+        length,
+        wrong_length: skipped,
+        invalid,
+    };
+    Ok((possible_words, skipped))
+}
+
+// A list of words that can be guessed but aren't possible answers, like Wordle's larger guess
+// list. The answers can always be guessed, so they are added at the end of the list.
+pub fn read_allowed_guesses(
+    path: &Path,
+    answers: &[String],
+    game: &Game,
+    invalid_words: InvalidWords,
+) -> Result<(Vec<String>, SkippedWords)> {
+    let (mut guesses, skipped) = read_file_from_path(path, game, invalid_words)?;
+    let listed: HashSet<String> = guesses.iter().cloned().collect();
+    guesses.extend(
+        answers
+            .iter()
+            .filter(|answer| !listed.contains(*answer))
+            .cloned(),
+    );
+    Ok((guesses, skipped))
+}
+
+// Words with the line they were on
+pub type NumberedWords = Vec<(usize, String)>;
+
+// Trimmed lower case words without blank lines and duplicates, in the order they're first
+// found. Words without the given length, and then words with characters that aren't letters of
+// the language, are returned separately with their line number.
+pub fn normalize_words<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    language: GameLanguage,
+    length: usize,
+) -> (Vec<String>, NumberedWords, NumberedWords) {
+    let mut seen = HashSet::new();
+    let mut words = Vec::new();
+    let mut skipped = Vec::new();
+    let mut invalid = Vec::new();
+    for (index, line) in lines.enumerate() {
+        let Some(word) = line.split_whitespace().next() else {
+            continue;
+        };
+        let word = word.to_lowercase();
+        if word.chars().count() != length {
+            skipped.push((index + 1, word));
+        } else if !word.chars().all(|letter| language.is_letter(letter)) {
+            invalid.push((index + 1, word));
+        } else if seen.insert(word.clone()) {
+            words.push(word);
+        }
+    }
+    (words, skipped, invalid)
+}
+
+fn first_lines(words: &[(usize, String)]) -> String {
+    words
+        .iter()
+        .take(5)
+        .map(|(line, word)| format!("line {}: {:?}", line, word))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn open_error(path: &Path) -> String {
+    format!(
+        "Failed to open word list {}, run from the directory it's in or pass --wordlist with the \
+         full path",
+        path.display()
+    )
+}
+
+// One word per line, so the file can be read back with --wordlist or searched with other tools
+pub fn write_words(path: &Path, words: &[String]) -> Result<()> {
+    let mut text = String::new();
+    for word in words {
+        text.push_str(word);
+        text.push('\n');
+    }
+    fs::write(path, text).with_context(|| format!("Failed to write words to {}", path.display()))
+}
+
+// Frequencies from a word list with lines like "slate 482913", words without one are left out
+pub fn read_frequencies(path: &Path) -> Result<HashMap<String, u64>> {
+    let file = fs::File::open(path).with_context(|| open_error(path))?;
+    let mut frequencies = HashMap::new();
+    for (index, line) in io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        let mut columns = line.split_whitespace();
+        if let (Some(word), Some(frequency)) = (columns.next(), columns.next()) {
+            let frequency = frequency.parse().with_context(|| {
+                format!(
+                    "Frequency {:?} on line {} of {} is not a number",
+                    frequency,
+                    index + 1,
+                    path.display()
+                )
+            })?;
+            frequencies.insert(word.to_lowercase(), frequency);
+        }
+    }
+    Ok(frequencies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english() -> Game {
+        Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_embedded_words() {
+        for (language, length) in [
+            (GameLanguage::English, GameLength::Five),
+            (GameLanguage::Swedish, GameLength::Five),
+            (GameLanguage::Swedish, GameLength::Six),
+            (GameLanguage::German, GameLength::Five),
+            (GameLanguage::Spanish, GameLength::Five),
+            (GameLanguage::French, GameLength::Five),
+            (GameLanguage::Italian, GameLength::Five),
+            (GameLanguage::Portuguese, GameLength::Five),
+            (GameLanguage::Norwegian, GameLength::Five),
+            (GameLanguage::Danish, GameLength::Five),
+        ] {
+            let game = Game::new(language, length, vec!['-'; length as usize], vec![]);
+            let words = read_file(&game, None).unwrap();
+            assert!(!words.is_empty());
+            assert!(words
+                .iter()
+                .all(|word| word.chars().count() == length as usize));
+        }
+    }
+
+    #[test]
+    fn test_embedded_list_hash() {
+        assert_eq!(content_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(content_hash("a"), 0xaf63_dc4c_8601_ec8c);
+        for list in EMBEDDED_LISTS {
+            assert_eq!(list.hash(), content_hash(list.words));
+            assert_eq!(embedded_words(list.language, list.length), Some(list.words));
+            let on_disk = fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join(list.file));
+            assert_eq!(on_disk.unwrap(), list.words, "{}", list.file);
+        }
+        let english = &EMBEDDED_LISTS[0];
+        assert_eq!(english.word_count(), ENGLISH_FIVE.lines().count());
+        assert_ne!(english.hash(), EMBEDDED_LISTS[1].hash());
+    }
+
+    #[test]
+    fn test_word_list_cache() {
+        let game = |language, length: GameLength| {
+            Game::new(language, length, vec!['-'; length as usize], vec![])
+        };
+        let mut cache = WordListCache::default();
+        let english = game(GameLanguage::English, GameLength::Five);
+        let first = cache.read(&english, None).unwrap().0;
+        assert_eq!(cache.read(&english, None).unwrap().0, first);
+        assert_eq!(cache.lists.len(), 1);
+
+        let swedish_five = cache
+            .read(&game(GameLanguage::Swedish, GameLength::Five), None)
+            .unwrap()
+            .0;
+        let swedish_six = cache
+            .read(&game(GameLanguage::Swedish, GameLength::Six), None)
+            .unwrap()
+            .0;
+        assert_ne!(swedish_five, first);
+        assert_ne!(swedish_six, swedish_five);
+        assert_eq!(cache.lists.len(), 3);
+        assert!(cache
+            .read(&game(GameLanguage::German, GameLength::Seven), None)
+            .is_err());
+        assert_eq!(cache.lists.len(), 3);
+    }
+
+    #[test]
+    fn test_word_buffer() {
+        let mut game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            "S---E".chars().collect(),
+            vec!['t', 'r'],
+        );
+        game.excluded_positions[1].push('l');
+        game.update_letter_counts();
+        let words = read_file(&game, None).unwrap();
+        let buffer = WordBuffer::new(&words);
+        assert_eq!(buffer.len(), words.len());
+        assert_eq!(buffer.get(0), Some(words[0].as_str()));
+        assert_eq!(buffer.to_strings(), words);
+
+        let solved = crate::solve(&game, &words);
+        assert!(!solved.is_empty());
+        assert_eq!(crate::solve(&game, &buffer.words()), solved);
+        let mut cache = WordListCache::default();
+        assert_eq!(cache.read_buffer(&game, None).unwrap().0, &buffer);
+        assert!(WordBuffer::new(Vec::<String>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_merge_words() {
+        let words = ["slate", "crane", "pious"].map(String::from);
+        let extra = ["crane", "fjord", "slate", "nymph"].map(String::from);
+        assert_eq!(
+            merge_words(&words, &extra),
+            ["slate", "crane", "pious", "fjord", "nymph"]
+        );
+        assert_eq!(merge_words(&words, &[]), words);
+
+        // Extra words are normalized like the word list, and words of other lengths skipped
+        let path = std::env::temp_dir().join("wordlehelper_test_extra_wordlist.txt");
+        fs::write(
+            &path,
+            "Slate
+zzzzz
+qajaqs
+",
+        )
+        .unwrap();
+        let game = Game::new(
+            GameLanguage::English,
+            GameLength::Five,
+            vec!['-'; 5],
+            vec![],
+        );
+        let bundled = read_file(&game, None).unwrap();
+        let mut cache = WordListCache::new(InvalidWords::default(), Some(path.clone()));
+        let (merged, skipped) = cache.read(&game, None).unwrap();
+        assert_eq!(merged.len(), bundled.len() + 1);
+        assert_eq!(merged.last().map(String::as_str), Some("zzzzz"));
+        assert_eq!(skipped[1].wrong_length, [(3, "qajaqs".to_string())]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_allowed_guesses() {
+        let path = std::env::temp_dir().join("wordlehelper_test_read_allowed_guesses.txt");
+        fs::write(&path, "aahed\nslate\n").unwrap();
+        let answers = ["slate", "crane"].map(String::from);
+        assert_eq!(
+            read_allowed_guesses(&path, &answers, &english(), InvalidWords::Skip)
+                .unwrap()
+                .0,
+            ["aahed", "slate", "crane"]
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_file_from_path() {
+        let path = std::env::temp_dir().join("wordlehelper_test_read_file_from_path.txt");
+        let read = |path| read_file_from_path(path, &english(), InvalidWords::Skip);
+        fs::write(&path, "slate\ncrane\n").unwrap();
+        let (words, skipped) = read(&path).unwrap();
+        assert_eq!(words, ["slate", "crane"]);
+        assert!(skipped.warnings().is_empty());
+
+        fs::write(&path, "slate\ncat\ncrane\nhorses\n").unwrap();
+        let (words, skipped) = read(&path).unwrap();
+        assert_eq!(words, ["slate", "crane"]);
+        assert_eq!(skipped.wrong_length.len(), 2);
+        let warnings = skipped.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Skipped 2 words in"));
+        assert!(warnings[0].contains("line 2: \"cat\""));
+        fs::write(&path, "cat\nhorses\n").unwrap();
+        assert!(read(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_crlf_line_endings() {
+        let path = std::env::temp_dir().join("wordlehelper_test_crlf_line_endings.txt");
+        fs::write(&path, "slate\r\ncrane 1200\r\n\r\ntrace\r").unwrap();
+        let words = read_file_from_path(&path, &english(), InvalidWords::Reject)
+            .unwrap()
+            .0;
+        assert_eq!(words, ["slate", "crane", "trace"]);
+        assert!(words.iter().all(|word| !word.contains('\r')));
+        assert_eq!(infer_length(&path).unwrap(), GameLength::Five);
+        assert_eq!(read_frequencies(&path).unwrap()["crane"], 1200);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_words() {
+        let path = std::env::temp_dir().join("wordlehelper_test_invalid_words.txt");
+        fs::write(&path, "slate\ncan't\nx-ray\ncrane\n").unwrap();
+        let (words, skipped) = read_file_from_path(&path, &english(), InvalidWords::Skip).unwrap();
+        assert_eq!(words, ["slate", "crane"]);
+        assert_eq!(skipped.invalid.len(), 2);
+        assert!(skipped.warnings()[0].contains("other characters than letters"));
+        let error = read_file_from_path(&path, &english(), InvalidWords::Reject).unwrap_err();
+        assert!(error.to_string().starts_with("2 words in"));
+        assert!(error.to_string().contains("line 2: \"can't\""));
+        fs::remove_file(&path).unwrap();
+
+        let (words, skipped, invalid) = normalize_words(
+            ["can't", "slate", "müsli"].into_iter(),
+            GameLanguage::English,
+            5,
+        );
+        assert_eq!(words, ["slate"]);
+        assert!(skipped.is_empty());
+        assert_eq!(
+            invalid,
+            [(1, "can't".to_string()), (3, "müsli".to_string())]
+        );
+        let (words, _, _) = normalize_words(["müsli"].into_iter(), GameLanguage::German, 5);
+        assert_eq!(words, ["müsli"]);
+    }
+
+    #[test]
+    fn test_missing_word_list() {
+        let path = std::env::temp_dir().join("wordlehelper_test_missing_word_list.txt");
+        let _ = fs::remove_file(&path);
+        for error in [
+            read_file_from_path(&path, &english(), InvalidWords::Skip).unwrap_err(),
+            infer_length(&path).unwrap_err(),
+            read_frequencies(&path).unwrap_err(),
+        ] {
+            let message = format!("{:#}", error);
+            assert!(message.contains("wordlehelper_test_missing_word_list.txt"));
+            assert!(message.contains("--wordlist"));
+        }
+    }
+
+    #[test]
+    fn test_normalize_words() {
+        let lines = [
+            "  Slate ",
+            "",
+            "crane\t12",
+            "CRANE",
+            "cat",
+            "slate",
+            "\t",
+            "horses",
+        ];
+        let (words, skipped, _) = normalize_words(lines.into_iter(), GameLanguage::English, 5);
+        assert_eq!(words, ["slate", "crane"]);
+        assert_eq!(skipped, [(5, "cat".to_string()), (8, "horses".to_string())]);
+    }
+
+    #[test]
+    fn test_german_words() {
+        let game = Game::new(GameLanguage::German, GameLength::Five, vec!['-'; 5], vec![]);
+        let words = read_file(&game, None).unwrap();
+        assert!(words.contains(&"grüße".to_string()));
+        assert!(words.contains(&"schoß".to_string()));
+    }
+
+    #[test]
+    fn test_infer_length() {
+        let path = std::env::temp_dir().join("wordlehelper_test_infer_length.txt");
+        fs::write(&path, "\nlove\nrope\n").unwrap();
+        assert_eq!(infer_length(&path).unwrap(), GameLength::Four);
+        fs::remove_file(&path).unwrap();
+
+        let game = Game::new(GameLanguage::English, GameLength::Six, vec!['-'; 6], vec![]);
+        assert!(read_file(&game, None).is_err());
+    }
+
+    #[test]
+    fn test_write_words() {
+        let path = std::env::temp_dir().join("wordlehelper_test_write_words.txt");
+        let words = ["slate", "crane", "shale"].map(String::from);
+        write_words(&path, &words).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "slate\ncrane\nshale\n");
+        assert_eq!(
+            read_file_from_path(&path, &english(), InvalidWords::Reject)
+                .unwrap()
+                .0,
+            words
+        );
+        write_words(&path, &[]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        fs::remove_file(&path).unwrap();
+
+        let missing_dir = std::env::temp_dir().join("wordlehelper_missing_dir/words.txt");
+        assert!(write_words(&missing_dir, &words).is_err());
+    }
+
+    #[test]
+    fn test_read_frequencies() {
+        let path = std::env::temp_dir().join("wordlehelper_test_read_frequencies.txt");
+        fs::write(&path, "slate 482913\ncrane 1200\nxylyl\n").unwrap();
+        assert_eq!(
+            read_file_from_path(&path, &english(), InvalidWords::Skip)
+                .unwrap()
+                .0,
+            ["slate", "crane", "xylyl"]
+        );
+        let frequencies = read_frequencies(&path).unwrap();
+        assert_eq!(frequencies.len(), 2);
+        assert_eq!(frequencies["slate"], 482_913);
+
+        fs::write(&path, "slate many\n").unwrap();
+        assert!(read_frequencies(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}